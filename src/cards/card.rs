@@ -0,0 +1,273 @@
+use serde::{Serialize, Deserialize};
+use crate::components::{GraphChange, DistributionTransform};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CardType {
+    Infrastructure,
+    Process,
+    Architecture,
+    Culture,
+    Maintenance,
+}
+
+impl CardType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Infrastructure => "Infrastructure",
+            Self::Process => "Process",
+            Self::Architecture => "Architecture",
+            Self::Culture => "Culture",
+            Self::Maintenance => "Maintenance",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Infrastructure => "🏗️",
+            Self::Process => "📋",
+            Self::Architecture => "🏛️",
+            Self::Culture => "👥",
+            Self::Maintenance => "🧹",
+        }
+    }
+}
+
+// Which nodes an effect applies to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum NodeSelector {
+    Named(String),
+    All,
+    CriticalPath,
+    WithAttribute(String),
+}
+
+// Which edges an effect applies to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EdgeSelector {
+    Between { from: String, to: String },
+    Touching(String),
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NodeField {
+    Health,
+    TechDebt,
+    ContagionRisk,
+    OperatingCost,
+    DefectRate,
+    LatencyMean,
+    LatencySpread,
+    FailureRateMean,
+    FailureRateSpread,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EdgeField {
+    Reliability,
+    TechDebtSpread,
+    Bandwidth,
+    LatencyMean,
+    LatencySpread,
+    FailureRateMean,
+    FailureRateSpread,
+}
+
+// A distribution on a node or edge that can be reshaped as a whole
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DistributionField {
+    Latency,
+    FailureRate,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EffectTarget {
+    Node { selector: NodeSelector, field: NodeField },
+    Edge { selector: EdgeSelector, field: EdgeField },
+    // Reshape a distribution with a transform. The effect's modification
+    // and magnitude don't apply: the transform says what happens.
+    NodeDistribution { selector: NodeSelector, field: DistributionField, transform: DistributionTransform },
+    EdgeDistribution { selector: EdgeSelector, field: DistributionField, transform: DistributionTransform },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModificationType {
+    Add,
+    Multiply,
+    Set,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EffectDuration {
+    Instant,
+    Sprints(u32),
+    Permanent,
+}
+
+// Kind of structural change an Architecture card lets the player make
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RestructureKind {
+    SplitNode,
+    MergeNodes,
+    RemoveNode,
+    RetargetEdge,
+}
+
+impl RestructureKind {
+    pub fn matches(&self, change: &GraphChange) -> bool {
+        matches!(
+            (self, change),
+            (Self::SplitNode, GraphChange::SplitNode { .. })
+                | (Self::MergeNodes, GraphChange::MergeNodes { .. })
+                | (Self::RemoveNode, GraphChange::RemoveNode(_))
+                | (Self::RetargetEdge, GraphChange::RetargetEdge { .. })
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardEffect {
+    pub target: EffectTarget,
+    pub modification: ModificationType,
+    pub magnitude: f64,
+    pub duration: EffectDuration,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DevOpsCard {
+    pub name: String,
+    pub card_type: CardType,
+    pub description: String,
+    pub cost: u32,               // DevOps points to play
+    pub maintenance_cost: f64,   // Money per sprint while the card is in effect
+    pub primary_effect: CardEffect,
+    pub side_effects: Vec<CardEffect>,
+    pub prerequisites: Vec<String>,  // Names of cards that must have been played first
+    #[serde(default)]
+    pub restructure: Option<RestructureKind>,  // Structural change the card performs, if any
+    #[serde(default)]
+    pub fixes_defects: u32,  // Open defects fixed outright when the card is applied, worst first
+}
+
+impl DevOpsCard {
+    // A card that only has its headline effect; the `with_` methods add the
+    // rest, so new fields only need touching on the cards that use them
+    pub fn new(name: &str, card_type: CardType, description: &str, cost: u32, primary_effect: CardEffect) -> Self {
+        Self {
+            name: name.into(),
+            card_type,
+            description: description.into(),
+            cost,
+            maintenance_cost: 0.0,
+            primary_effect,
+            side_effects: Vec::new(),
+            prerequisites: Vec::new(),
+            restructure: None,
+            fixes_defects: 0,
+        }
+    }
+
+    pub fn with_maintenance_cost(mut self, maintenance_cost: f64) -> Self {
+        self.maintenance_cost = maintenance_cost;
+        self
+    }
+
+    pub fn with_side_effects(mut self, side_effects: Vec<CardEffect>) -> Self {
+        self.side_effects = side_effects;
+        self
+    }
+
+    pub fn with_prerequisites(mut self, prerequisites: &[&str]) -> Self {
+        self.prerequisites = prerequisites.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
+    pub fn with_restructure(mut self, restructure: RestructureKind) -> Self {
+        self.restructure = Some(restructure);
+        self
+    }
+
+    pub fn with_defect_fixes(mut self, fixes_defects: u32) -> Self {
+        self.fixes_defects = fixes_defects;
+        self
+    }
+
+    // Primary effect followed by all side effects
+    pub fn effects(&self) -> impl Iterator<Item = &CardEffect> {
+        std::iter::once(&self.primary_effect).chain(self.side_effects.iter())
+    }
+
+    pub fn prerequisites_met(&self, played: &[String]) -> bool {
+        self.prerequisites.iter().all(|req| played.contains(req))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitoring_card() -> DevOpsCard {
+        let primary_effect = CardEffect {
+            target: EffectTarget::Node {
+                selector: NodeSelector::All,
+                field: NodeField::FailureRateSpread,
+            },
+            modification: ModificationType::Multiply,
+            magnitude: 0.7,
+            duration: EffectDuration::Sprints(3),
+        };
+        DevOpsCard::new("Deploy Monitoring", CardType::Infrastructure, "Reduces failure variance", 2, primary_effect)
+            .with_maintenance_cost(100.0)
+            .with_side_effects(vec![CardEffect {
+                target: EffectTarget::Node {
+                    selector: NodeSelector::All,
+                    field: NodeField::OperatingCost,
+                },
+                modification: ModificationType::Add,
+                magnitude: 20.0,
+                duration: EffectDuration::Permanent,
+            }])
+            .with_prerequisites(&["Basic Infrastructure"])
+    }
+
+    #[test]
+    fn test_effects_include_primary_and_side_effects() {
+        let card = monitoring_card();
+        let effects: Vec<_> = card.effects().collect();
+        assert_eq!(effects.len(), 2);
+        assert_eq!(effects[0], &card.primary_effect);
+    }
+
+    #[test]
+    fn test_prerequisites() {
+        let card = monitoring_card();
+        assert!(!card.prerequisites_met(&[]));
+        assert!(card.prerequisites_met(&["Basic Infrastructure".to_string()]));
+    }
+
+    #[test]
+    fn test_new_cards_start_plain() {
+        let card = DevOpsCard::new("Hotfix", CardType::Maintenance, "Patch it", 0, monitoring_card().primary_effect);
+        assert_eq!(card.maintenance_cost, 0.0);
+        assert!(card.side_effects.is_empty() && card.prerequisites.is_empty());
+        assert_eq!(card.restructure, None);
+        assert_eq!(card.fixes_defects, 0);
+
+        let card = card.with_restructure(RestructureKind::RemoveNode).with_defect_fixes(2);
+        assert_eq!(card.restructure, Some(RestructureKind::RemoveNode));
+        assert_eq!(card.fixes_defects, 2);
+    }
+
+    #[test]
+    fn test_restructure_kind_matches_change() {
+        let split = GraphChange::SplitNode {
+            node: "core".into(),
+            new_name: "core_b".into(),
+            moved_neighbors: vec![],
+        };
+        assert!(RestructureKind::SplitNode.matches(&split));
+        assert!(!RestructureKind::MergeNodes.matches(&split));
+    }
+}
+
+// Easter egg: "Every card in this deck was play-tested against a production outage at 3am 🃏"
@@ -0,0 +1,261 @@
+use crate::components::DistributionTransform;
+use super::card::{
+    DevOpsCard, CardType, CardEffect, EffectTarget, EffectDuration, ModificationType,
+    NodeSelector, EdgeSelector, NodeField, EdgeField, DistributionField, RestructureKind,
+};
+
+fn node_effect(
+    selector: NodeSelector,
+    field: NodeField,
+    modification: ModificationType,
+    magnitude: f64,
+    duration: EffectDuration,
+) -> CardEffect {
+    CardEffect {
+        target: EffectTarget::Node { selector, field },
+        modification,
+        magnitude,
+        duration,
+    }
+}
+
+fn edge_effect(
+    selector: EdgeSelector,
+    field: EdgeField,
+    modification: ModificationType,
+    magnitude: f64,
+    duration: EffectDuration,
+) -> CardEffect {
+    CardEffect {
+        target: EffectTarget::Edge { selector, field },
+        modification,
+        magnitude,
+        duration,
+    }
+}
+
+fn edge_transform(
+    selector: EdgeSelector,
+    field: DistributionField,
+    transform: DistributionTransform,
+    duration: EffectDuration,
+) -> CardEffect {
+    CardEffect {
+        target: EffectTarget::EdgeDistribution { selector, field, transform },
+        modification: ModificationType::Set,  // Unused: the transform is the whole effect
+        magnitude: 0.0,
+        duration,
+    }
+}
+
+// The cards every new game starts with
+pub fn starter_deck() -> Vec<DevOpsCard> {
+    use EffectDuration::{Instant, Permanent, Sprints};
+    use ModificationType::{Add, Multiply};
+
+    vec![
+        DevOpsCard::new(
+            "Deploy Monitoring",
+            CardType::Infrastructure,
+            "Narrows the failure distribution of every component",
+            2,
+            node_effect(NodeSelector::All, NodeField::FailureRateSpread, Multiply, 0.7, Sprints(3)),
+        )
+        .with_maintenance_cost(50.0)
+        .with_side_effects(vec![
+            node_effect(NodeSelector::All, NodeField::OperatingCost, Add, 20.0, Sprints(3)),
+        ]),
+        DevOpsCard::new(
+            "Add Redundancy",
+            CardType::Infrastructure,
+            "Shifts critical path failures towards reliability",
+            3,
+            node_effect(NodeSelector::CriticalPath, NodeField::FailureRateMean, Add, -0.5, Permanent),
+        )
+        .with_maintenance_cost(100.0)
+        .with_side_effects(vec![
+            node_effect(NodeSelector::CriticalPath, NodeField::OperatingCost, Multiply, 1.2, Permanent),
+        ]),
+        DevOpsCard::new(
+            "Scale Horizontally",
+            CardType::Infrastructure,
+            "Adds headroom to critical services for a couple of sprints",
+            3,
+            node_effect(NodeSelector::CriticalPath, NodeField::FailureRateMean, Add, -0.3, Sprints(2)),
+        )
+        .with_side_effects(vec![
+            node_effect(NodeSelector::CriticalPath, NodeField::OperatingCost, Multiply, 1.25, Sprints(2)),
+        ]),
+        DevOpsCard::new(
+            "Implement Caching",
+            CardType::Infrastructure,
+            "Cuts latency on the money-making path",
+            2,
+            node_effect(NodeSelector::CriticalPath, NodeField::LatencyMean, Multiply, 0.8, Permanent),
+        )
+        .with_maintenance_cost(30.0)
+        .with_side_effects(vec![
+            node_effect(NodeSelector::CriticalPath, NodeField::ContagionRisk, Add, 0.05, Permanent),
+        ]),
+        DevOpsCard::new(
+            "Automate Tests",
+            CardType::Process,
+            "Halves the defect rate of every component",
+            2,
+            node_effect(NodeSelector::All, NodeField::DefectRate, Multiply, 0.5, Permanent),
+        )
+        .with_maintenance_cost(30.0),
+        DevOpsCard::new(
+            "Implement CI/CD",
+            CardType::Process,
+            "Slows the spread of tech debt across dependencies",
+            3,
+            edge_effect(EdgeSelector::All, EdgeField::TechDebtSpread, Multiply, 0.7, Permanent),
+        )
+        .with_maintenance_cost(80.0)
+        .with_side_effects(vec![
+            node_effect(NodeSelector::All, NodeField::DefectRate, Multiply, 0.9, Permanent),
+        ])
+        .with_prerequisites(&["Automate Tests"]),
+        DevOpsCard::new(
+            "Document Systems",
+            CardType::Process,
+            "Makes every component less contagious",
+            1,
+            node_effect(NodeSelector::All, NodeField::ContagionRisk, Multiply, 0.8, Permanent),
+        ),
+        DevOpsCard::new(
+            "Refactor System",
+            CardType::Architecture,
+            "Pays down tech debt at the cost of short-term stability",
+            3,
+            node_effect(NodeSelector::All, NodeField::TechDebt, Add, -20.0, Instant),
+        )
+        .with_side_effects(vec![
+            node_effect(NodeSelector::All, NodeField::Health, Add, -5.0, Instant),
+        ]),
+        DevOpsCard::new(
+            "Implement Microservices",
+            CardType::Architecture,
+            "Split a component in two, sharing its dependencies between the halves",
+            4,
+            node_effect(NodeSelector::All, NodeField::ContagionRisk, Multiply, 0.9, Permanent),
+        )
+        .with_maintenance_cost(50.0)
+        .with_side_effects(vec![
+            node_effect(NodeSelector::All, NodeField::LatencyMean, Multiply, 1.1, Permanent),
+        ])
+        .with_prerequisites(&["Implement CI/CD"])
+        .with_restructure(RestructureKind::SplitNode),
+        DevOpsCard::new(
+            "Consolidate Services",
+            CardType::Architecture,
+            "Merge two components to cut operating overhead",
+            3,
+            node_effect(NodeSelector::All, NodeField::OperatingCost, Multiply, 0.95, Permanent),
+        )
+        .with_restructure(RestructureKind::MergeNodes),
+        DevOpsCard::new(
+            "Decommission Service",
+            CardType::Architecture,
+            "Switch a component off; whatever called it loses that dependency",
+            2,
+            node_effect(NodeSelector::All, NodeField::TechDebt, Add, -5.0, Instant),
+        )
+        .with_side_effects(vec![
+            node_effect(NodeSelector::All, NodeField::Health, Add, -5.0, Instant),
+        ])
+        .with_prerequisites(&["Document Systems"])
+        .with_restructure(RestructureKind::RemoveNode),
+        DevOpsCard::new(
+            "Reroute Dependency",
+            CardType::Architecture,
+            "Point a component at a different dependency",
+            2,
+            edge_effect(EdgeSelector::All, EdgeField::TechDebtSpread, Multiply, 0.9, Permanent),
+        )
+        .with_side_effects(vec![
+            edge_effect(EdgeSelector::All, EdgeField::Reliability, Multiply, 0.98, Sprints(1)),
+        ])
+        .with_restructure(RestructureKind::RetargetEdge),
+        DevOpsCard::new(
+            "Bug Bash",
+            CardType::Process,
+            "Everyone drops what they're doing to fix the three worst bugs",
+            2,
+            node_effect(NodeSelector::All, NodeField::DefectRate, Multiply, 0.95, Sprints(1)),
+        )
+        .with_defect_fixes(3),
+        DevOpsCard::new(
+            "Blameless Postmortems",
+            CardType::Culture,
+            "The team learns from incidents instead of hiding them",
+            1,
+            node_effect(NodeSelector::All, NodeField::DefectRate, Multiply, 0.9, Sprints(3)),
+        ),
+        DevOpsCard::new(
+            "Chaos Engineering",
+            CardType::Culture,
+            "Break things on purpose so they break less by accident",
+            2,
+            node_effect(NodeSelector::All, NodeField::FailureRateSpread, Multiply, 0.8, Permanent),
+        )
+        .with_maintenance_cost(40.0)
+        .with_side_effects(vec![
+            node_effect(NodeSelector::CriticalPath, NodeField::Health, Add, -10.0, Instant),
+        ])
+        .with_prerequisites(&["Deploy Monitoring"]),
+        DevOpsCard::new(
+            "Knowledge Sharing",
+            CardType::Culture,
+            "Fewer silos means debt stays where it started",
+            1,
+            edge_effect(EdgeSelector::All, EdgeField::TechDebtSpread, Multiply, 0.9, Sprints(3)),
+        ),
+        DevOpsCard::new(
+            "Circuit Breakers",
+            CardType::Infrastructure,
+            "Calls give up after 250ms instead of waiting on a slow dependency",
+            2,
+            edge_transform(
+                EdgeSelector::All,
+                DistributionField::Latency,
+                DistributionTransform::Clamp { min: 0.0, max: 250.0 },
+                Permanent,
+            ),
+        )
+        .with_maintenance_cost(30.0)
+        .with_side_effects(vec![
+            node_effect(NodeSelector::All, NodeField::OperatingCost, Add, 5.0, Permanent),
+        ])
+        .with_prerequisites(&["Deploy Monitoring"]),
+        DevOpsCard::new(
+            "Hotfix",
+            CardType::Maintenance,
+            "Patch the critical path now, pay for it later",
+            0,
+            node_effect(NodeSelector::CriticalPath, NodeField::Health, Add, 15.0, Instant),
+        )
+        .with_side_effects(vec![
+            node_effect(NodeSelector::CriticalPath, NodeField::TechDebt, Add, 5.0, Instant),
+        ]),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starter_deck_prerequisites_exist() {
+        let deck = starter_deck();
+        let names: Vec<String> = deck.iter().map(|c| c.name.clone()).collect();
+        for card in &deck {
+            for req in &card.prerequisites {
+                assert!(names.contains(req), "{} requires unknown card {}", card.name, req);
+            }
+        }
+    }
+}
+
+// Easter egg: "The Mythical Man-Month card was left out of the starter deck on purpose 📚"
@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::{Serialize, Deserialize};
+use super::card::DevOpsCard;
+use super::catalog::starter_deck;
+
+pub const DEFAULT_HAND_SIZE: usize = 5;
+
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Deck {
+    pub draw_pile: Vec<DevOpsCard>,  // Top of the pile is the end of the Vec
+    pub hand: Vec<DevOpsCard>,
+    pub discard_pile: Vec<DevOpsCard>,
+    pub played: Vec<String>,  // Names of every card played this game
+    pub hand_size: usize,
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new(starter_deck())
+    }
+}
+
+impl Deck {
+    pub fn new(cards: Vec<DevOpsCard>) -> Self {
+        Self {
+            draw_pile: cards,
+            hand: Vec::new(),
+            discard_pile: Vec::new(),
+            played: Vec::new(),
+            hand_size: DEFAULT_HAND_SIZE,
+        }
+    }
+
+    pub fn total_cards(&self) -> usize {
+        self.draw_pile.len() + self.hand.len() + self.discard_pile.len()
+    }
+
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.draw_pile.shuffle(rng);
+    }
+
+    // Draw up to `count` cards, reshuffling the discard pile when the draw pile runs out.
+    // Returns the number of cards actually drawn.
+    pub fn draw<R: Rng + ?Sized>(&mut self, count: usize, rng: &mut R) -> usize {
+        let mut drawn = 0;
+        while drawn < count {
+            if self.draw_pile.is_empty() {
+                if self.discard_pile.is_empty() {
+                    break;
+                }
+                self.draw_pile.append(&mut self.discard_pile);
+                self.shuffle(rng);
+            }
+            if let Some(card) = self.draw_pile.pop() {
+                self.hand.push(card);
+                drawn += 1;
+            }
+        }
+        drawn
+    }
+
+    pub fn discard_hand(&mut self) {
+        self.discard_pile.append(&mut self.hand);
+    }
+
+    // Discard the current hand and draw a fresh one
+    pub fn draw_hand<R: Rng + ?Sized>(&mut self, rng: &mut R) -> usize {
+        self.discard_hand();
+        self.draw(self.hand_size, rng)
+    }
+
+    // Move a card from the hand to the discard pile and record it as played
+    pub fn play(&mut self, hand_index: usize) -> Option<DevOpsCard> {
+        let card = self.take_from_hand(hand_index)?;
+        self.record_played(card.clone());
+        Some(card)
+    }
+
+    // Remove a card from the hand without playing it (e.g. to queue it)
+    pub fn take_from_hand(&mut self, hand_index: usize) -> Option<DevOpsCard> {
+        if hand_index >= self.hand.len() {
+            return None;
+        }
+        Some(self.hand.remove(hand_index))
+    }
+
+    pub fn return_to_hand(&mut self, card: DevOpsCard) {
+        self.hand.push(card);
+    }
+
+    // Put a card that took effect on the discard pile
+    pub fn record_played(&mut self, card: DevOpsCard) {
+        self.played.push(card.name.clone());
+        self.discard_pile.push(card);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_draw_hand() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut deck = Deck::default();
+        let total = deck.total_cards();
+
+        assert_eq!(deck.draw_hand(&mut rng), DEFAULT_HAND_SIZE);
+        assert_eq!(deck.hand.len(), DEFAULT_HAND_SIZE);
+        assert_eq!(deck.total_cards(), total);
+
+        // Drawing a new hand discards the old one
+        deck.draw_hand(&mut rng);
+        assert_eq!(deck.hand.len(), DEFAULT_HAND_SIZE);
+        assert_eq!(deck.discard_pile.len(), DEFAULT_HAND_SIZE);
+        assert_eq!(deck.total_cards(), total);
+    }
+
+    #[test]
+    fn test_draw_reshuffles_discard_pile() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut deck = Deck::default();
+        let total = deck.total_cards();
+
+        for _ in 0..10 {
+            deck.draw_hand(&mut rng);
+            assert_eq!(deck.hand.len(), DEFAULT_HAND_SIZE);
+            assert_eq!(deck.total_cards(), total);
+        }
+    }
+
+    #[test]
+    fn test_draw_stops_when_deck_exhausted() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut deck = Deck::new(starter_deck().into_iter().take(2).collect());
+        assert_eq!(deck.draw(5, &mut rng), 2);
+        assert!(deck.draw_pile.is_empty());
+    }
+
+    #[test]
+    fn test_play_card() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut deck = Deck::default();
+        deck.draw_hand(&mut rng);
+
+        let name = deck.hand[0].name.clone();
+        let card = deck.play(0).expect("card in hand");
+        assert_eq!(card.name, name);
+        assert_eq!(deck.hand.len(), DEFAULT_HAND_SIZE - 1);
+        assert_eq!(deck.discard_pile.last().map(|c| c.name.as_str()), Some(name.as_str()));
+        assert_eq!(deck.played, vec![name]);
+
+        assert!(deck.play(99).is_none());
+    }
+}
+
+// Easter egg: "Shuffled with the same entropy that powers our on-call rotation 🔀"
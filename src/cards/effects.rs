@@ -0,0 +1,657 @@
+use bevy::prelude::*;
+use petgraph::stable_graph::{NodeIndex, EdgeIndex};
+use serde::{Serialize, Deserialize};
+use std::fmt;
+use crate::components::{
+    SystemGraph, SystemNode, SystemEdge, DistributionType, DistributionError, DistributionTransform,
+};
+use super::card::{
+    DevOpsCard, CardEffect, EffectTarget, EffectDuration, ModificationType,
+    NodeSelector, EdgeSelector, NodeField, EdgeField, DistributionField,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EffectError {
+    UnknownNode(String),
+    UnknownEdge { from: String, to: String },
+    // The field is a distribution with no parameter the effect can move on its own
+    NotAdjustable { element: String, field: String },
+    // A transform would leave the distribution invalid, e.g. truncating away all of it
+    InvalidDistribution { element: String, error: DistributionError },
+}
+
+impl fmt::Display for EffectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownNode(name) => write!(f, "unknown node '{}'", name),
+            Self::UnknownEdge { from, to } => write!(f, "no edge from '{}' to '{}'", from, to),
+            Self::NotAdjustable { element, field } => write!(f, "{} of '{}' can't be adjusted", field, element),
+            Self::InvalidDistribution { element, error } => write!(f, "can't reshape '{}': {}", element, error),
+        }
+    }
+}
+
+impl std::error::Error for EffectError {}
+
+// The concrete field an effect touched. Elements are identified by name so
+// changes can still be found after the graph is restructured.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ChangeTarget {
+    Node { name: String, field: NodeField },
+    Edge { from: String, to: String, name: String, field: EdgeField },
+    NodeDistribution { name: String, field: DistributionField, transform: DistributionTransform },
+    EdgeDistribution { from: String, to: String, name: String, field: DistributionField, transform: DistributionTransform },
+}
+
+// A single applied change, recorded as the delta actually written after clamping.
+// Distribution changes are undone through their transform and have no delta.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub target: ChangeTarget,
+    pub delta: f64,
+}
+
+// An effect that is still influencing the system and will be unwound later
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveEffect {
+    pub source: String,
+    pub effect: CardEffect,
+    pub remaining_sprints: Option<u32>,  // None for permanent effects
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActiveEffects {
+    pub effects: Vec<ActiveEffect>,
+}
+
+impl ActiveEffects {
+    // Apply a single effect and track it if it has a duration
+    pub fn apply(
+        &mut self,
+        system: &mut SystemGraph,
+        source: &str,
+        effect: &CardEffect,
+    ) -> Result<(), EffectError> {
+        let changes = apply_effect(system, effect)?;
+        let remaining_sprints = match effect.duration {
+            EffectDuration::Instant => return Ok(()),
+            EffectDuration::Sprints(sprints) => Some(sprints),
+            EffectDuration::Permanent => None,
+        };
+        self.effects.push(ActiveEffect {
+            source: source.to_string(),
+            effect: effect.clone(),
+            remaining_sprints,
+            changes,
+        });
+        Ok(())
+    }
+
+    // Apply every effect of a card. Either all effects apply or none do.
+    pub fn apply_card(&mut self, system: &mut SystemGraph, card: &DevOpsCard) -> Result<(), EffectError> {
+        let mut applied: Vec<Vec<FieldChange>> = Vec::new();
+        for effect in card.effects() {
+            match apply_effect(system, effect) {
+                Ok(changes) => applied.push(changes),
+                Err(err) => {
+                    for changes in applied.iter().rev() {
+                        revert_changes(system, changes);
+                    }
+                    return Err(err);
+                }
+            }
+        }
+
+        for (effect, changes) in card.effects().zip(applied) {
+            let remaining_sprints = match effect.duration {
+                EffectDuration::Instant => continue,
+                EffectDuration::Sprints(sprints) => Some(sprints),
+                EffectDuration::Permanent => None,
+            };
+            self.effects.push(ActiveEffect {
+                source: card.name.clone(),
+                effect: effect.clone(),
+                remaining_sprints,
+                changes,
+            });
+        }
+        Ok(())
+    }
+
+    // Count down timed effects and unwind the ones that ran out.
+    // Returns the effects that expired.
+    pub fn end_sprint(&mut self, system: &mut SystemGraph) -> Vec<ActiveEffect> {
+        for active in self.effects.iter_mut() {
+            if let Some(remaining) = active.remaining_sprints.as_mut() {
+                *remaining = remaining.saturating_sub(1);
+            }
+        }
+
+        let (expired, still_active): (Vec<_>, Vec<_>) = self.effects
+            .drain(..)
+            .partition(|active| active.remaining_sprints == Some(0));
+        self.effects = still_active;
+
+        for active in expired.iter().rev() {
+            revert_changes(system, &active.changes);
+        }
+        expired
+    }
+
+    // Unwind every active effect from the given source, permanent ones included
+    pub fn remove_source(&mut self, system: &mut SystemGraph, source: &str) -> usize {
+        let (removed, kept): (Vec<_>, Vec<_>) = self.effects
+            .drain(..)
+            .partition(|active| active.source == source);
+        self.effects = kept;
+
+        for active in removed.iter().rev() {
+            revert_changes(system, &active.changes);
+        }
+        removed.len()
+    }
+}
+
+// Apply an effect to every field it targets, returning what was changed
+pub fn apply_effect(system: &mut SystemGraph, effect: &CardEffect) -> Result<Vec<FieldChange>, EffectError> {
+    let mut changes = Vec::new();
+    match &effect.target {
+        EffectTarget::Node { selector, field } => {
+            for idx in select_nodes(system, selector)? {
+                let node = &mut system.graph[idx];
+                let name = node.name.clone();
+                let (value, min, max) = node_field(node, *field)
+                    .ok_or_else(|| EffectError::NotAdjustable { element: name, field: format!("{:?}", field) })?;
+                let delta = modify(value, effect.modification, effect.magnitude, min, max);
+                changes.push(FieldChange {
+                    target: ChangeTarget::Node { name: system.graph[idx].name.clone(), field: *field },
+                    delta,
+                });
+            }
+        }
+        EffectTarget::Edge { selector, field } => {
+            for idx in select_edges(system, selector)? {
+                let (from, to) = edge_names(system, idx);
+                let edge = &mut system.graph[idx];
+                let name = edge.name.clone();
+                let (value, min, max) = edge_field(edge, *field)
+                    .ok_or_else(|| EffectError::NotAdjustable { element: name, field: format!("{:?}", field) })?;
+                let delta = modify(value, effect.modification, effect.magnitude, min, max);
+                changes.push(FieldChange {
+                    target: ChangeTarget::Edge { from, to, name: system.graph[idx].name.clone(), field: *field },
+                    delta,
+                });
+            }
+        }
+        EffectTarget::NodeDistribution { selector, field, transform } => {
+            // Work out every new distribution first so a bad one changes nothing
+            let reshaped = select_nodes(system, selector)?.into_iter()
+                .map(|idx| {
+                    let current = node_distribution(&mut system.graph[idx], *field).clone();
+                    reshape(&current, transform, &system.graph[idx].name).map(|dist| (idx, dist))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            for (idx, dist) in reshaped {
+                let node = &mut system.graph[idx];
+                *node_distribution(node, *field) = dist;
+                changes.push(FieldChange {
+                    target: ChangeTarget::NodeDistribution { name: node.name.clone(), field: *field, transform: transform.clone() },
+                    delta: 0.0,
+                });
+            }
+        }
+        EffectTarget::EdgeDistribution { selector, field, transform } => {
+            let reshaped = select_edges(system, selector)?.into_iter()
+                .map(|idx| {
+                    let current = edge_distribution(&mut system.graph[idx], *field).clone();
+                    reshape(&current, transform, &system.graph[idx].name).map(|dist| (idx, dist))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            for (idx, dist) in reshaped {
+                let (from, to) = edge_names(system, idx);
+                let edge = &mut system.graph[idx];
+                *edge_distribution(edge, *field) = dist;
+                changes.push(FieldChange {
+                    target: ChangeTarget::EdgeDistribution {
+                        from, to, name: edge.name.clone(), field: *field, transform: transform.clone(),
+                    },
+                    delta: 0.0,
+                });
+            }
+        }
+    }
+    Ok(changes)
+}
+
+fn reshape(dist: &DistributionType, transform: &DistributionTransform, element: &str) -> Result<DistributionType, EffectError> {
+    transform.apply(dist)
+        .map_err(|error| EffectError::InvalidDistribution { element: element.to_string(), error })
+}
+
+// Undo previously applied changes. Only the recorded delta is removed, so any
+// drift that happened since (e.g. tech debt spread) is preserved. Elements that
+// no longer exist are skipped.
+pub fn revert_changes(system: &mut SystemGraph, changes: &[FieldChange]) {
+    for change in changes.iter().rev() {
+        match &change.target {
+            ChangeTarget::Node { name, field } => {
+                if let Some(&idx) = system.node_indices.get(name) {
+                    if let Some((value, min, max)) = node_field(&mut system.graph[idx], *field) {
+                        *value = (*value - change.delta).clamp(min, max);
+                    }
+                }
+            }
+            ChangeTarget::Edge { from, to, name, field } => {
+                if let Some(idx) = find_edge(system, from, to, name) {
+                    if let Some((value, min, max)) = edge_field(&mut system.graph[idx], *field) {
+                        *value = (*value - change.delta).clamp(min, max);
+                    }
+                }
+            }
+            // Transforms layered on since stay in place; only this one is peeled off
+            ChangeTarget::NodeDistribution { name, field, transform } => {
+                if let Some(&idx) = system.node_indices.get(name) {
+                    let dist = node_distribution(&mut system.graph[idx], *field);
+                    if let Some(reverted) = transform.revert(dist) {
+                        *dist = reverted;
+                    }
+                }
+            }
+            ChangeTarget::EdgeDistribution { from, to, name, field, transform } => {
+                if let Some(idx) = find_edge(system, from, to, name) {
+                    let dist = edge_distribution(&mut system.graph[idx], *field);
+                    if let Some(reverted) = transform.revert(dist) {
+                        *dist = reverted;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn modify(value: &mut f64, modification: ModificationType, magnitude: f64, min: f64, max: f64) -> f64 {
+    let old = *value;
+    let new = match modification {
+        ModificationType::Add => old + magnitude,
+        ModificationType::Multiply => old * magnitude,
+        ModificationType::Set => magnitude,
+    };
+    *value = new.clamp(min, max);
+    *value - old
+}
+
+fn distribution_field(dist: &mut DistributionType, spread: bool) -> Option<(&mut f64, f64, f64)> {
+    if spread {
+        let (min, max) = dist.spread_bounds();
+        dist.spread_mut().map(|value| (value, min, max))
+    } else {
+        let (min, max) = dist.location_bounds();
+        dist.location_mut().map(|value| (value, min, max))
+    }
+}
+
+// Mutable reference to a node field along with its valid range, if it can be adjusted
+fn node_field(node: &mut SystemNode, field: NodeField) -> Option<(&mut f64, f64, f64)> {
+    Some(match field {
+        NodeField::Health => (&mut node.health, 0.0, 100.0),
+        NodeField::TechDebt => (&mut node.tech_debt, 0.0, 100.0),
+        NodeField::ContagionRisk => (&mut node.contagion_risk, 0.0, 1.0),
+        NodeField::OperatingCost => (&mut node.operating_cost, 0.0, f64::INFINITY),
+        NodeField::DefectRate => (&mut node.defect_rate, 0.0, f64::INFINITY),
+        NodeField::LatencyMean => return distribution_field(&mut node.latency, false),
+        NodeField::LatencySpread => return distribution_field(&mut node.latency, true),
+        NodeField::FailureRateMean => return distribution_field(&mut node.failure_rate, false),
+        NodeField::FailureRateSpread => return distribution_field(&mut node.failure_rate, true),
+    })
+}
+
+// Mutable reference to an edge field along with its valid range, if it can be adjusted
+fn edge_field(edge: &mut SystemEdge, field: EdgeField) -> Option<(&mut f64, f64, f64)> {
+    Some(match field {
+        EdgeField::Reliability => (&mut edge.reliability, 0.0, 1.0),
+        EdgeField::TechDebtSpread => (&mut edge.tech_debt_spread, 0.0, 1.0),
+        EdgeField::Bandwidth => (&mut edge.bandwidth, 0.0, f64::INFINITY),
+        EdgeField::LatencyMean => return distribution_field(&mut edge.latency, false),
+        EdgeField::LatencySpread => return distribution_field(&mut edge.latency, true),
+        EdgeField::FailureRateMean => return distribution_field(&mut edge.failure_rate, false),
+        EdgeField::FailureRateSpread => return distribution_field(&mut edge.failure_rate, true),
+    })
+}
+
+fn node_distribution(node: &mut SystemNode, field: DistributionField) -> &mut DistributionType {
+    match field {
+        DistributionField::Latency => &mut node.latency,
+        DistributionField::FailureRate => &mut node.failure_rate,
+    }
+}
+
+fn edge_distribution(edge: &mut SystemEdge, field: DistributionField) -> &mut DistributionType {
+    match field {
+        DistributionField::Latency => &mut edge.latency,
+        DistributionField::FailureRate => &mut edge.failure_rate,
+    }
+}
+
+fn select_nodes(system: &SystemGraph, selector: &NodeSelector) -> Result<Vec<NodeIndex>, EffectError> {
+    let nodes = match selector {
+        NodeSelector::Named(name) => {
+            let idx = system.node_indices.get(name)
+                .ok_or_else(|| EffectError::UnknownNode(name.clone()))?;
+            vec![*idx]
+        }
+        NodeSelector::All => system.graph.node_indices().collect(),
+        NodeSelector::CriticalPath => system.graph.node_indices()
+            .filter(|idx| system.graph[*idx].critical_path)
+            .collect(),
+        NodeSelector::WithAttribute(attribute) => system.graph.node_indices()
+            .filter(|idx| system.graph[*idx].attributes.contains(attribute))
+            .collect(),
+    };
+    Ok(nodes)
+}
+
+fn select_edges(system: &SystemGraph, selector: &EdgeSelector) -> Result<Vec<EdgeIndex>, EffectError> {
+    let edges = match selector {
+        EdgeSelector::Between { from, to } => {
+            let from_idx = system.node_indices.get(from)
+                .ok_or_else(|| EffectError::UnknownNode(from.clone()))?;
+            let to_idx = system.node_indices.get(to)
+                .ok_or_else(|| EffectError::UnknownNode(to.clone()))?;
+            let edges: Vec<EdgeIndex> = system.graph.edge_indices()
+                .filter(|idx| system.graph.edge_endpoints(*idx) == Some((*from_idx, *to_idx)))
+                .collect();
+            if edges.is_empty() {
+                return Err(EffectError::UnknownEdge { from: from.clone(), to: to.clone() });
+            }
+            edges
+        }
+        EdgeSelector::Touching(name) => {
+            let node_idx = system.node_indices.get(name)
+                .ok_or_else(|| EffectError::UnknownNode(name.clone()))?;
+            system.graph.edge_indices()
+                .filter(|idx| {
+                    system.graph.edge_endpoints(*idx)
+                        .map(|(a, b)| a == *node_idx || b == *node_idx)
+                        .unwrap_or(false)
+                })
+                .collect()
+        }
+        EdgeSelector::All => system.graph.edge_indices().collect(),
+    };
+    Ok(edges)
+}
+
+fn edge_names(system: &SystemGraph, idx: EdgeIndex) -> (String, String) {
+    let (from, to) = system.graph.edge_endpoints(idx).expect("edge index from this graph");
+    (system.graph[from].name.clone(), system.graph[to].name.clone())
+}
+
+fn find_edge(system: &SystemGraph, from: &str, to: &str, name: &str) -> Option<EdgeIndex> {
+    let from_idx = *system.node_indices.get(from)?;
+    let to_idx = *system.node_indices.get(to)?;
+    system.graph.edge_indices().find(|idx| {
+        system.graph.edge_endpoints(*idx) == Some((from_idx, to_idx))
+            && system.graph[*idx].name == name
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::starter_deck;
+    use crate::test_utils::create_test_graph;
+
+    fn node<'a>(system: &'a SystemGraph, name: &str) -> &'a SystemNode {
+        &system.graph[system.node_indices[name]]
+    }
+
+    fn tech_debt_effect(selector: NodeSelector, magnitude: f64, duration: EffectDuration) -> CardEffect {
+        CardEffect {
+            target: EffectTarget::Node { selector, field: NodeField::TechDebt },
+            modification: ModificationType::Add,
+            magnitude,
+            duration,
+        }
+    }
+
+    #[test]
+    fn test_instant_effect_is_not_tracked() {
+        let mut system = create_test_graph();
+        let mut active = ActiveEffects::default();
+
+        let effect = tech_debt_effect(NodeSelector::Named("test_node_1".into()), -5.0, EffectDuration::Instant);
+        active.apply(&mut system, "Refactor", &effect).unwrap();
+
+        assert_eq!(node(&system, "test_node_1").tech_debt, 5.0);
+        assert!(active.effects.is_empty());
+    }
+
+    #[test]
+    fn test_values_are_clamped() {
+        let mut system = create_test_graph();
+        let effect = tech_debt_effect(NodeSelector::All, -50.0, EffectDuration::Instant);
+        let changes = apply_effect(&mut system, &effect).unwrap();
+
+        assert_eq!(node(&system, "test_node_1").tech_debt, 0.0);
+        assert_eq!(node(&system, "test_node_2").tech_debt, 0.0);
+        // Only the amount actually removed is recorded
+        assert_eq!(changes[0].delta, -10.0);
+        assert_eq!(changes[1].delta, 0.0);
+    }
+
+    #[test]
+    fn test_timed_effect_expires() {
+        let mut system = create_test_graph();
+        let mut active = ActiveEffects::default();
+
+        let effect = tech_debt_effect(NodeSelector::CriticalPath, 20.0, EffectDuration::Sprints(2));
+        active.apply(&mut system, "Crunch Time", &effect).unwrap();
+        assert_eq!(node(&system, "test_node_1").tech_debt, 30.0);
+        assert_eq!(node(&system, "test_node_2").tech_debt, 0.0);
+
+        assert!(active.end_sprint(&mut system).is_empty());
+        assert_eq!(node(&system, "test_node_1").tech_debt, 30.0);
+
+        let expired = active.end_sprint(&mut system);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(node(&system, "test_node_1").tech_debt, 10.0);
+        assert!(active.effects.is_empty());
+    }
+
+    #[test]
+    fn test_revert_preserves_drift() {
+        let mut system = create_test_graph();
+        let mut active = ActiveEffects::default();
+
+        let effect = tech_debt_effect(NodeSelector::Named("test_node_1".into()), 20.0, EffectDuration::Sprints(1));
+        active.apply(&mut system, "Crunch Time", &effect).unwrap();
+
+        // Something else raises the debt while the effect is active
+        let idx = system.node_indices["test_node_1"];
+        system.graph[idx].tech_debt += 5.0;
+
+        active.end_sprint(&mut system);
+        assert_eq!(node(&system, "test_node_1").tech_debt, 15.0);
+    }
+
+    #[test]
+    fn test_permanent_effect_stays_until_removed() {
+        let mut system = create_test_graph();
+        let mut active = ActiveEffects::default();
+
+        let effect = CardEffect {
+            target: EffectTarget::Edge { selector: EdgeSelector::All, field: EdgeField::TechDebtSpread },
+            modification: ModificationType::Multiply,
+            magnitude: 0.5,
+            duration: EffectDuration::Permanent,
+        };
+        active.apply(&mut system, "Implement CI/CD", &effect).unwrap();
+
+        for _ in 0..10 {
+            active.end_sprint(&mut system);
+        }
+        let spread = system.graph.edge_weights().next().unwrap().tech_debt_spread;
+        assert!((spread - 0.05).abs() < 1e-12);
+
+        assert_eq!(active.remove_source(&mut system, "Implement CI/CD"), 1);
+        let spread = system.graph.edge_weights().next().unwrap().tech_debt_spread;
+        assert!((spread - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_distribution_fields() {
+        let mut system = create_test_graph();
+        let idx = system.node_indices["test_node_1"];
+        system.graph[idx].failure_rate = DistributionType::LogNormal { location: -3.0, scale: 0.5 };
+
+        let effect = CardEffect {
+            target: EffectTarget::Node {
+                selector: NodeSelector::Named("test_node_1".into()),
+                field: NodeField::FailureRateSpread,
+            },
+            modification: ModificationType::Multiply,
+            magnitude: 0.5,
+            duration: EffectDuration::Instant,
+        };
+        apply_effect(&mut system, &effect).unwrap();
+
+        match system.graph[idx].failure_rate {
+            DistributionType::LogNormal { location, scale } => {
+                assert_eq!(location, -3.0);
+                assert_eq!(scale, 0.25);
+            }
+            _ => panic!("distribution type should not change"),
+        }
+    }
+
+    #[test]
+    fn test_distribution_transforms_apply_and_unwind() {
+        let mut system = create_test_graph();
+        let mut active = ActiveEffects::default();
+        let original = node(&system, "test_node_1").latency.clone();
+
+        // Timeouts cap latency, and a later shift stays on once they're removed
+        let timeouts = CardEffect {
+            target: EffectTarget::NodeDistribution {
+                selector: NodeSelector::CriticalPath,
+                field: DistributionField::Latency,
+                transform: DistributionTransform::Clamp { min: -1.0, max: 1.0 },
+            },
+            modification: ModificationType::Set,
+            magnitude: 0.0,
+            duration: EffectDuration::Sprints(1),
+        };
+        active.apply(&mut system, "Timeouts", &timeouts).unwrap();
+        assert_eq!(node(&system, "test_node_1").latency.quantile(0.999), 1.0);
+        assert_eq!(node(&system, "test_node_2").latency, original);
+
+        let shift = DistributionTransform::ShiftMean(5.0);
+        let slower = CardEffect {
+            target: EffectTarget::NodeDistribution {
+                selector: NodeSelector::Named("test_node_1".into()),
+                field: DistributionField::Latency,
+                transform: shift.clone(),
+            },
+            ..timeouts.clone()
+        };
+        apply_effect(&mut system, &slower).unwrap();
+
+        active.end_sprint(&mut system);
+        assert_eq!(node(&system, "test_node_1").latency, shift.apply(&original).unwrap());
+
+        // An impossible reshape is an error and leaves every node as it was
+        let before = system.clone();
+        let impossible = CardEffect {
+            target: EffectTarget::NodeDistribution {
+                selector: NodeSelector::All,
+                field: DistributionField::FailureRate,
+                transform: DistributionTransform::Truncate { min: 100.0, max: 200.0 },
+            },
+            ..timeouts
+        };
+        assert!(matches!(
+            apply_effect(&mut system, &impossible),
+            Err(EffectError::InvalidDistribution { .. })
+        ));
+        assert_eq!(system.graph.node_weights().collect::<Vec<_>>(), before.graph.node_weights().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_deploy_monitoring_unwinds_cleanly() {
+        let mut system = crate::components::create_architecture(crate::components::ArchitectureType::Monolith);
+        let before: Vec<SystemNode> = system.graph.node_weights().cloned().collect();
+        let mut active = ActiveEffects::default();
+
+        let card = starter_deck().into_iter()
+            .find(|c| c.name == "Deploy Monitoring")
+            .unwrap();
+        active.apply_card(&mut system, &card).unwrap();
+        assert_eq!(active.effects.len(), 2);
+
+        for _ in 0..3 {
+            active.end_sprint(&mut system);
+        }
+        assert!(active.effects.is_empty());
+
+        for (old, new) in before.iter().zip(system.graph.node_weights()) {
+            assert!((old.operating_cost - new.operating_cost).abs() < 1e-9);
+            let (mut old_rate, mut new_rate) = (old.failure_rate.clone(), new.failure_rate.clone());
+            assert!((*old_rate.spread_mut().unwrap() - *new_rate.spread_mut().unwrap()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_unknown_targets_are_errors() {
+        let mut system = create_test_graph();
+        let effect = tech_debt_effect(NodeSelector::Named("coffee_machine".into()), 5.0, EffectDuration::Instant);
+        assert_eq!(
+            apply_effect(&mut system, &effect),
+            Err(EffectError::UnknownNode("coffee_machine".into()))
+        );
+
+        let effect = CardEffect {
+            target: EffectTarget::Edge {
+                selector: EdgeSelector::Between { from: "test_node_2".into(), to: "test_node_1".into() },
+                field: EdgeField::Reliability,
+            },
+            modification: ModificationType::Add,
+            magnitude: 0.1,
+            duration: EffectDuration::Instant,
+        };
+        assert!(matches!(apply_effect(&mut system, &effect), Err(EffectError::UnknownEdge { .. })));
+
+        // An exponential's spread is tied to its rate, so there's nothing to narrow
+        let idx = system.node_indices["test_node_1"];
+        system.graph[idx].failure_rate = DistributionType::Exponential { rate: 2.0 };
+        let effect = CardEffect {
+            target: EffectTarget::Node {
+                selector: NodeSelector::Named("test_node_1".into()),
+                field: NodeField::FailureRateSpread,
+            },
+            modification: ModificationType::Multiply,
+            magnitude: 0.5,
+            duration: EffectDuration::Instant,
+        };
+        assert_eq!(
+            apply_effect(&mut system, &effect).unwrap_err().to_string(),
+            "FailureRateSpread of 'test_node_1' can't be adjusted"
+        );
+    }
+
+    #[test]
+    fn test_failed_card_leaves_graph_untouched() {
+        let mut system = create_test_graph();
+        let mut active = ActiveEffects::default();
+        let mut card = starter_deck().into_iter().find(|c| c.name == "Hotfix").unwrap();
+        card.side_effects.push(tech_debt_effect(
+            NodeSelector::Named("missing".into()), 1.0, EffectDuration::Permanent));
+
+        assert!(active.apply_card(&mut system, &card).is_err());
+        assert_eq!(node(&system, "test_node_1").health, 100.0);
+        assert_eq!(node(&system, "test_node_1").tech_debt, 10.0);
+        assert!(active.effects.is_empty());
+    }
+}
+
+// Easter egg: "Every effect here is reversible. Unlike that one deploy on a Friday afternoon 🔁"
@@ -0,0 +1,33 @@
+mod card;
+mod catalog;
+mod deck;
+mod effects;
+
+pub use card::{
+    DevOpsCard,
+    CardType,
+    CardEffect,
+    EffectTarget,
+    EffectDuration,
+    ModificationType,
+    NodeSelector,
+    EdgeSelector,
+    NodeField,
+    EdgeField,
+    DistributionField,
+    RestructureKind,
+};
+
+pub use catalog::starter_deck;
+
+pub use deck::{Deck, DEFAULT_HAND_SIZE};
+
+pub use effects::{
+    ActiveEffects,
+    ActiveEffect,
+    FieldChange,
+    ChangeTarget,
+    EffectError,
+    apply_effect,
+    revert_changes,
+};
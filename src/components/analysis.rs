@@ -0,0 +1,269 @@
+use petgraph::algo::{dominators::simple_fast, tarjan_scc};
+use petgraph::stable_graph::NodeIndex;
+use petgraph::Direction;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use super::critical_path::traffic_entry_points;
+use super::system_graph::SystemGraph;
+
+// What the shape of the graph says about one component
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeAnalysis {
+    pub single_point_of_failure: bool,  // Removing it splits the system in two
+    pub dominators: Vec<String>,  // Every request reaching it passes through these, nearest first
+    pub blast_radius: Vec<String>,  // Dependents that go down when it does
+    pub centrality: f64,  // Share of shortest routes between other components that run through it
+    pub in_cycle: bool,
+}
+
+// Dependency analysis of a whole system, keyed by component name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphAnalysis {
+    pub nodes: BTreeMap<String, NodeAnalysis>,
+    pub cycles: Vec<Vec<String>>,  // Groups of components that (transitively) depend on each other
+}
+
+impl GraphAnalysis {
+    pub fn of(system: &SystemGraph) -> Self {
+        let graph = &system.graph;
+        let cut = articulation_points(system);
+        let dominators = dominators(system);
+        let centrality = betweenness_centrality(system);
+        let cycles = cycles(system);
+        let in_cycle: BTreeSet<NodeIndex> = cycles.iter().flatten().copied().collect();
+
+        let names = |indices: Vec<NodeIndex>| indices.into_iter().map(|idx| graph[idx].name.clone()).collect();
+        let nodes = graph.node_indices()
+            .map(|idx| (graph[idx].name.clone(), NodeAnalysis {
+                single_point_of_failure: cut.contains(&idx),
+                dominators: names(dominators.get(&idx).cloned().unwrap_or_default()),
+                blast_radius: names(blast_radius(system, idx)),
+                centrality: centrality.get(&idx).copied().unwrap_or(0.0),
+                in_cycle: in_cycle.contains(&idx),
+            }))
+            .collect();
+        Self { nodes, cycles: cycles.into_iter().map(names).collect() }
+    }
+
+    pub fn node(&self, name: &str) -> Option<&NodeAnalysis> {
+        self.nodes.get(name)
+    }
+
+    pub fn single_points_of_failure(&self) -> impl Iterator<Item = &str> {
+        self.nodes.iter()
+            .filter(|(_, node)| node.single_point_of_failure)
+            .map(|(name, _)| name.as_str())
+    }
+
+    // Highest centrality in the system, for scaling it to 0..1 on screen
+    pub fn max_centrality(&self) -> f64 {
+        self.nodes.values().map(|node| node.centrality).fold(0.0, f64::max)
+    }
+}
+
+// Nodes whose removal disconnects the system, ignoring edge direction
+// (Tarjan's low-link search). Returned in graph order.
+pub fn articulation_points(system: &SystemGraph) -> Vec<NodeIndex> {
+    let mut search = LowLink { system, order: HashMap::new(), low: HashMap::new(), cut: BTreeSet::new() };
+    for root in system.graph.node_indices() {
+        if !search.order.contains_key(&root) && search.visit(root, None) > 1 {
+            search.cut.insert(root);
+        }
+    }
+    system.graph.node_indices().filter(|idx| search.cut.contains(idx)).collect()
+}
+
+struct LowLink<'a> {
+    system: &'a SystemGraph,
+    order: HashMap<NodeIndex, usize>,
+    low: HashMap<NodeIndex, usize>,
+    cut: BTreeSet<NodeIndex>,
+}
+
+impl LowLink<'_> {
+    // Depth-first visit; returns how many DFS children `node` has
+    fn visit(&mut self, node: NodeIndex, parent: Option<NodeIndex>) -> usize {
+        let index = self.order.len();
+        self.order.insert(node, index);
+        self.low.insert(node, index);
+
+        let neighbors: BTreeSet<NodeIndex> = self.system.graph.neighbors_undirected(node)
+            .filter(|&next| next != node && Some(next) != parent)
+            .collect();
+        let mut children = 0;
+        for next in neighbors {
+            let reached = match self.order.get(&next) {
+                Some(&seen) => seen,
+                None => {
+                    children += 1;
+                    self.visit(next, Some(node));
+                    if parent.is_some() && self.low[&next] >= index {
+                        self.cut.insert(node);
+                    }
+                    self.low[&next]
+                }
+            };
+            let low = self.low.get_mut(&node).expect("visited");
+            *low = (*low).min(reached);
+        }
+        children
+    }
+}
+
+// For every node reachable from an entry point, the nodes every request to it
+// must pass through, nearest first. Entry points themselves have none.
+pub fn dominators(system: &SystemGraph) -> HashMap<NodeIndex, Vec<NodeIndex>> {
+    // A virtual front door in front of every entry point, so there's one root
+    let mut scratch = system.graph.map(|_, _| (), |_, _| ());
+    let root = scratch.add_node(());
+    for entry in traffic_entry_points(system) {
+        scratch.add_edge(root, entry, ());
+    }
+    let tree = simple_fast(&scratch, root);
+
+    system.graph.node_indices()
+        .filter_map(|idx| {
+            let mut chain = Vec::new();
+            let mut current = tree.immediate_dominator(idx)?;
+            while current != root {
+                chain.push(current);
+                current = tree.immediate_dominator(current)?;
+            }
+            Some((idx, chain))
+        })
+        .collect()
+}
+
+// Everything that goes down when `node` does: its transitive dependents.
+// Edges point at dependencies, so this walks them backwards.
+pub fn blast_radius(system: &SystemGraph, node: NodeIndex) -> Vec<NodeIndex> {
+    let mut seen = BTreeSet::from([node]);
+    let mut queue = VecDeque::from([node]);
+    while let Some(current) = queue.pop_front() {
+        for dependent in system.graph.neighbors_directed(current, Direction::Incoming) {
+            if seen.insert(dependent) {
+                queue.push_back(dependent);
+            }
+        }
+    }
+    system.graph.node_indices().filter(|&idx| idx != node && seen.contains(&idx)).collect()
+}
+
+// Brandes' betweenness over directed, unweighted edges, normalised so 1.0
+// means every shortest route between two other components runs through it
+pub fn betweenness_centrality(system: &SystemGraph) -> HashMap<NodeIndex, f64> {
+    let graph = &system.graph;
+    let mut centrality: HashMap<NodeIndex, f64> = graph.node_indices().map(|idx| (idx, 0.0)).collect();
+
+    for source in graph.node_indices() {
+        let mut visited = Vec::new();
+        let mut predecessors: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+        let mut paths: HashMap<NodeIndex, f64> = HashMap::from([(source, 1.0)]);
+        let mut distance: HashMap<NodeIndex, usize> = HashMap::from([(source, 0)]);
+        let mut queue = VecDeque::from([source]);
+
+        while let Some(current) = queue.pop_front() {
+            visited.push(current);
+            let next_distance = distance[&current] + 1;
+            for next in graph.neighbors_directed(current, Direction::Outgoing) {
+                let reached = *distance.entry(next).or_insert_with(|| {
+                    queue.push_back(next);
+                    next_distance
+                });
+                if reached == next_distance {
+                    *paths.entry(next).or_insert(0.0) += paths[&current];
+                    predecessors.entry(next).or_default().push(current);
+                }
+            }
+        }
+
+        let mut dependency: HashMap<NodeIndex, f64> = HashMap::new();
+        for &node in visited.iter().rev() {
+            let through = dependency.get(&node).copied().unwrap_or(0.0);
+            for &previous in predecessors.get(&node).into_iter().flatten() {
+                *dependency.entry(previous).or_insert(0.0) += paths[&previous] / paths[&node] * (1.0 + through);
+            }
+            if node != source {
+                *centrality.get_mut(&node).expect("every node has an entry") += through;
+            }
+        }
+    }
+
+    let n = graph.node_count() as f64;
+    if n > 2.0 {
+        for value in centrality.values_mut() {
+            *value /= (n - 1.0) * (n - 2.0);
+        }
+    }
+    centrality
+}
+
+// Groups of nodes that depend on each other, each in graph order. A node
+// calling itself counts as a cycle of one.
+pub fn cycles(system: &SystemGraph) -> Vec<Vec<NodeIndex>> {
+    let graph = &system.graph;
+    let mut cycles: Vec<Vec<NodeIndex>> = tarjan_scc(graph).into_iter()
+        .filter(|group| group.len() > 1 || graph.find_edge(group[0], group[0]).is_some())
+        .map(|mut group| { group.sort(); group })
+        .collect();
+    cycles.sort();
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{SystemNode, SystemEdge};
+
+    // gateway -> api -> {db, cache}, worker -> db
+    fn create_system() -> SystemGraph {
+        let mut system = SystemGraph::new();
+        for name in ["gateway", "api", "db", "cache", "worker"] {
+            system.add_node(SystemNode { name: name.into(), ..Default::default() });
+        }
+        system.add_edge("gateway", "api", SystemEdge::default());
+        system.add_edge("api", "db", SystemEdge::default());
+        system.add_edge("api", "cache", SystemEdge::default());
+        system.add_edge("worker", "db", SystemEdge::default());
+        system
+    }
+
+    #[test]
+    fn test_single_points_of_failure_and_blast_radius() {
+        let analysis = GraphAnalysis::of(&create_system());
+
+        assert_eq!(analysis.single_points_of_failure().collect::<Vec<_>>(), vec!["api", "db"]);
+        assert_eq!(analysis.node("db").unwrap().blast_radius, vec!["gateway", "api", "worker"]);
+        assert_eq!(analysis.node("cache").unwrap().blast_radius, vec!["gateway", "api"]);
+        assert!(analysis.node("gateway").unwrap().blast_radius.is_empty());
+    }
+
+    #[test]
+    fn test_dominators_from_entry_points() {
+        let analysis = GraphAnalysis::of(&create_system());
+
+        assert_eq!(analysis.node("cache").unwrap().dominators, vec!["api", "gateway"]);
+        // Reachable from both the gateway and the worker, so nothing dominates it
+        assert!(analysis.node("db").unwrap().dominators.is_empty());
+        assert!(analysis.node("worker").unwrap().dominators.is_empty());
+    }
+
+    #[test]
+    fn test_centrality_and_cycles() {
+        let mut system = create_system();
+        let analysis = GraphAnalysis::of(&system);
+        // api sits between the gateway and both of its dependencies
+        assert_eq!(analysis.max_centrality(), analysis.node("api").unwrap().centrality);
+        assert_eq!(analysis.node("gateway").unwrap().centrality, 0.0);
+        assert!(analysis.cycles.is_empty());
+
+        system.add_edge("db", "api", SystemEdge::default());
+        let analysis = GraphAnalysis::of(&system);
+        assert_eq!(analysis.cycles, vec![vec!["api".to_string(), "db".to_string()]]);
+        assert!(analysis.node("db").unwrap().in_cycle);
+        assert!(!analysis.node("cache").unwrap().in_cycle);
+        // The cycle makes everything upstream of db depend on cache too
+        assert_eq!(analysis.node("cache").unwrap().blast_radius, vec!["gateway", "api", "db", "worker"]);
+    }
+}
+
+// Easter egg: "Every architecture diagram has a box labelled 'misc'. It's always the articulation point 🧷"
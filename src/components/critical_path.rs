@@ -0,0 +1,201 @@
+use petgraph::stable_graph::NodeIndex;
+use petgraph::Direction;
+use std::collections::{BTreeSet, VecDeque};
+use super::system_graph::{SystemGraph, SystemNode};
+
+// Marks a node where paying traffic ends, e.g. the store orders are written to
+pub const REVENUE_ATTRIBUTE: &str = "revenue";
+
+// Keeps a node on the critical path whatever the topology says, for the likes
+// of a Legacy COBOL System that nothing visibly routes through but the
+// business can't run without
+pub const CRITICAL_OVERRIDE_ATTRIBUTE: &str = "always_critical";
+
+// Marks a node customers call directly, for entry points that have callers of their own
+pub const ENTRY_POINT_ATTRIBUTE: &str = "entry_point";
+
+pub fn is_revenue_sink(node: &SystemNode) -> bool {
+    node.attributes.iter().any(|attribute| attribute == REVENUE_ATTRIBUTE)
+}
+
+pub fn is_pinned_critical(node: &SystemNode) -> bool {
+    node.attributes.iter().any(|attribute| attribute == CRITICAL_OVERRIDE_ATTRIBUTE)
+}
+
+// Gateways and nodes marked as entry points take traffic whether or not
+// anything calls them
+pub fn is_declared_entry_point(node: &SystemNode) -> bool {
+    node.node_type == "gateway" || node.attributes.iter().any(|attribute| attribute == ENTRY_POINT_ATTRIBUTE)
+}
+
+// Where customer traffic comes in, from the topology alone: gateways, nodes
+// marked as entry points, and nodes nothing calls. A revenue sink nothing
+// calls is just unreachable, not somewhere traffic starts. Request paths,
+// dominators and the critical path all start from here.
+pub fn traffic_entry_points(system: &SystemGraph) -> Vec<NodeIndex> {
+    let graph = &system.graph;
+    graph.node_indices()
+        .filter(|&idx| {
+            let node = &graph[idx];
+            let uncalled = graph.neighbors_directed(idx, Direction::Incoming).next().is_none();
+            is_declared_entry_point(node) || (uncalled && !is_revenue_sink(node))
+        })
+        .collect()
+}
+
+// Every node on some path from an entry point to a revenue sink, plus pinned
+// nodes. None when the system has no revenue sinks to derive it from.
+pub fn derive_critical_path(system: &SystemGraph) -> Option<BTreeSet<NodeIndex>> {
+    let graph = &system.graph;
+    let sinks: Vec<NodeIndex> = graph.node_indices().filter(|&idx| is_revenue_sink(&graph[idx])).collect();
+    if sinks.is_empty() {
+        return None;
+    }
+    let from_entries = reachable(system, traffic_entry_points(system), Direction::Outgoing);
+    let to_sinks = reachable(system, sinks, Direction::Incoming);
+    Some(graph.node_indices()
+        .filter(|idx| (from_entries.contains(idx) && to_sinks.contains(idx)) || is_pinned_critical(&graph[*idx]))
+        .collect())
+}
+
+fn reachable(system: &SystemGraph, start: Vec<NodeIndex>, direction: Direction) -> BTreeSet<NodeIndex> {
+    let mut seen: BTreeSet<NodeIndex> = start.iter().copied().collect();
+    let mut queue = VecDeque::from(start);
+    while let Some(current) = queue.pop_front() {
+        for next in system.graph.neighbors_directed(current, direction) {
+            if seen.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    seen
+}
+
+impl SystemGraph {
+    // Bring every node's critical_path flag in line with the topology. Without
+    // revenue sinks there's nothing to derive: flags an earlier derivation set
+    // are cleared, and only hand-set and pinned ones stay.
+    pub fn refresh_critical_path(&mut self) {
+        if self.deferring_refresh {
+            return;
+        }
+        let derived = derive_critical_path(self);
+        for idx in self.graph.node_indices().collect::<Vec<_>>() {
+            let was_derived = self.derived_critical.contains(&idx);
+            let node = &mut self.graph[idx];
+            node.critical_path = match &derived {
+                Some(critical) => critical.contains(&idx),
+                None => (node.critical_path && !was_derived) || is_pinned_critical(node),
+            };
+        }
+        self.derived_critical = derived.unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{GraphChange, SystemEdge};
+
+    fn node(name: &str, attributes: &[&str]) -> SystemNode {
+        SystemNode {
+            name: name.into(),
+            attributes: attributes.iter().map(|a| a.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    // gateway -> checkout -> {orders, cache}, plus a pinned cobol nothing calls
+    fn create_system() -> SystemGraph {
+        let mut system = SystemGraph::new();
+        system.add_node(node("gateway", &[]));
+        system.add_node(node("checkout", &[]));
+        system.add_node(node("orders", &[REVENUE_ATTRIBUTE]));
+        system.add_node(node("cache", &[]));
+        system.add_node(node("cobol", &[CRITICAL_OVERRIDE_ATTRIBUTE]));
+        system.add_edge("gateway", "checkout", SystemEdge::default());
+        system.add_edge("checkout", "orders", SystemEdge::default());
+        system.add_edge("checkout", "cache", SystemEdge::default());
+        system
+    }
+
+    fn critical(system: &SystemGraph) -> Vec<&str> {
+        system.graph.node_weights().filter(|n| n.critical_path).map(|n| n.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_paths_to_revenue_are_critical() {
+        let system = create_system();
+        // cobol is its own entry point but pinned; cache carries no paying traffic
+        assert_eq!(critical(&system), vec!["gateway", "checkout", "orders", "cobol"]);
+    }
+
+    #[test]
+    fn test_recalculated_on_structural_change() {
+        let mut system = create_system();
+        system.apply_change(&GraphChange::RetargetEdge {
+            from: "checkout".into(), to: "orders".into(), new_to: "cache".into(),
+        }).unwrap();
+        system.add_edge("cache", "orders", SystemEdge::default());
+        assert_eq!(critical(&system), vec!["gateway", "checkout", "orders", "cache", "cobol"]);
+
+        system.remove_node("cache").unwrap();
+        // Nothing reaches the orders store any more, so no traffic pays and only the pin remains
+        assert_eq!(critical(&system), vec!["cobol"]);
+    }
+
+    #[test]
+    fn test_cycles_regain_the_critical_path() {
+        // Every node has a caller, so only the gateway says where traffic enters
+        let mut system = SystemGraph::new();
+        system.add_node(SystemNode { name: "edge".into(), node_type: "gateway".into(), ..Default::default() });
+        system.add_node(node("checkout", &[]));
+        system.add_node(node("orders", &[REVENUE_ATTRIBUTE]));
+        system.add_edge("edge", "checkout", SystemEdge::default());
+        system.add_edge("checkout", "orders", SystemEdge::default());
+        system.add_edge("orders", "edge", SystemEdge::default());
+        assert_eq!(critical(&system), vec!["edge", "checkout", "orders"]);
+
+        system.remove_edge("checkout", "orders").unwrap();
+        assert!(critical(&system).is_empty());
+        system.add_edge("checkout", "orders", SystemEdge::default());
+        assert_eq!(critical(&system), vec!["edge", "checkout", "orders"]);
+    }
+
+    #[test]
+    fn test_removing_the_last_sink_clears_the_derived_path() {
+        let mut system = create_system();
+        system.remove_node("orders").unwrap();
+        // Only the pin is left; the old gateway -> checkout path no longer counts
+        assert_eq!(critical(&system), vec!["cobol"]);
+
+        // Flags set by hand once nothing is derived are left alone
+        system.node_mut("gateway").unwrap().critical_path = true;
+        system.add_edge("gateway", "cache", SystemEdge::default());
+        assert_eq!(critical(&system), vec!["gateway", "cobol"]);
+    }
+
+    #[test]
+    fn test_bulk_changes_derive_once_at_the_end() {
+        let mut system = SystemGraph::new();
+        system.in_bulk(|system| {
+            system.add_node(node("gateway", &[]));
+            system.add_node(node("orders", &[REVENUE_ATTRIBUTE]));
+            system.add_edge("gateway", "orders", SystemEdge::default());
+            assert!(critical(system).is_empty());
+        });
+        assert_eq!(critical(&system), vec!["gateway", "orders"]);
+    }
+
+    #[test]
+    fn test_hand_set_flags_survive_without_revenue_sinks() {
+        let mut system = SystemGraph::new();
+        system.add_node(SystemNode { name: "a".into(), critical_path: true, ..Default::default() });
+        system.add_node(node("b", &[]));
+        system.add_node(node("c", &[CRITICAL_OVERRIDE_ATTRIBUTE]));
+        system.add_edge("a", "b", SystemEdge::default());
+        assert_eq!(critical(&system), vec!["a", "c"]);
+    }
+}
+
+// Easter egg: "The critical path is whichever one the CEO's demo uses 🎤"
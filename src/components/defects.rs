@@ -0,0 +1,126 @@
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+use super::system_graph::SystemNode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Low => "Low",
+            Self::Medium => "Medium",
+            Self::High => "High",
+            Self::Critical => "Critical",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Low => "🐜",
+            Self::Medium => "🐛",
+            Self::High => "🪲",
+            Self::Critical => "🦂",
+        }
+    }
+
+    // Health an open defect of this severity eats from its node per simulated second
+    pub fn health_damage(&self) -> f64 {
+        match self {
+            Self::Low => 0.05,
+            Self::Medium => 0.15,
+            Self::High => 0.4,
+            Self::Critical => 1.0,
+        }
+    }
+
+    // Engineering effort needed to fix it
+    pub fn effort(&self) -> f64 {
+        match self {
+            Self::Low => 1.0,
+            Self::Medium => 2.0,
+            Self::High => 4.0,
+            Self::Critical => 8.0,
+        }
+    }
+
+    // Indebted code breeds nastier bugs, and anything on the critical path
+    // is one notch closer to a page at 3am
+    pub fn roll<R: Rng + ?Sized>(node: &SystemNode, rng: &mut R) -> Self {
+        let critical_bonus = if node.critical_path { 0.1 } else { 0.0 };
+        let score = rng.gen::<f64>() + node.tech_debt.clamp(0.0, 100.0) / 200.0 + critical_bonus;
+        if score < 0.5 {
+            Self::Low
+        } else if score < 0.8 {
+            Self::Medium
+        } else if score < 0.95 {
+            Self::High
+        } else {
+            Self::Critical
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DefectStatus {
+    Open,
+    Fixed,
+    Obsolete,  // The component it lived in was removed
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Defect {
+    pub id: u64,
+    pub origin: String,  // Node the defect lives in
+    pub severity: Severity,
+    pub status: DefectStatus,
+    pub age: f64,  // Simulated seconds since it was found
+    pub found_in_sprint: u32,
+    pub remaining_effort: f64,  // Engineering effort left before it is fixed
+}
+
+impl Defect {
+    pub fn new(id: u64, origin: impl Into<String>, severity: Severity, found_in_sprint: u32) -> Self {
+        Self {
+            id,
+            origin: origin.into(),
+            severity,
+            status: DefectStatus::Open,
+            age: 0.0,
+            found_in_sprint,
+            remaining_effort: severity.effort(),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status == DefectStatus::Open
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::SimRng;
+
+    #[test]
+    fn test_debt_makes_defects_worse() {
+        let mut rng = SimRng::new(11);
+        let clean = SystemNode::default();
+        let rotten = SystemNode { tech_debt: 100.0, critical_path: true, ..Default::default() };
+
+        let clean_rolls: Vec<_> = (0..500).map(|_| Severity::roll(&clean, &mut rng)).collect();
+        let rotten_rolls: Vec<_> = (0..500).map(|_| Severity::roll(&rotten, &mut rng)).collect();
+
+        let critical = |rolls: &[Severity]| rolls.iter().filter(|s| **s == Severity::Critical).count();
+        assert!(critical(&rotten_rolls) > critical(&clean_rolls) * 5);
+        // 100% debt on the critical path never produces a low severity bug
+        assert!(!rotten_rolls.contains(&Severity::Low));
+    }
+}
+
+// Easter egg: "It's not a bug, it's an undocumented severity level 🐞"
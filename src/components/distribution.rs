@@ -0,0 +1,616 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use rand_distr::{Distribution, Normal, LogNormal, Exp, Poisson, Beta, Weibull, Pareto};
+use serde::{Serialize, Deserialize};
+use statrs::distribution::{ContinuousCDF, DiscreteCDF};
+use statrs::function::erf::{erf, erf_inv};
+use statrs::function::gamma::gamma;
+use std::f64::consts::SQRT_2;
+use std::fmt;
+use super::latency::percentile;
+
+// Distribution wrapper that can be serialized
+#[derive(Component, Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub enum DistributionType {
+    Normal { mean: f64, std_dev: f64 },
+    LogNormal { location: f64, scale: f64 },
+    Exponential { rate: f64 },
+    Poisson { lambda: f64 },  // Discrete: whole numbers of events
+    Beta { alpha: f64, beta: f64 },  // Shares and probabilities, between 0 and 1
+    Weibull { scale: f64, shape: f64 },  // Wear-out: shape above 1 fails more with age
+    Pareto { scale: f64, shape: f64 },  // Heavy tail; no mean for shape <= 1
+    Empirical { samples: Vec<f64> },  // Replays observed values
+    // Reshaped distributions, built by the transforms in transforms.rs
+    Shifted { base: Box<DistributionType>, offset: f64 },
+    Scaled { base: Box<DistributionType>, factor: f64 },  // Spread around the base's mean
+    Clamped { base: Box<DistributionType>, min: f64, max: f64 },  // Values outside pile up at the bounds
+    Truncated { base: Box<DistributionType>, min: f64, max: f64 },  // Values outside never happen
+    Mixture { parts: Vec<(f64, DistributionType)> },  // Weights needn't add up to 1
+}
+
+// Points used to integrate moments numerically over the quantile function
+const MOMENT_STEPS: usize = 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DistributionError {
+    NotFinite { parameter: &'static str, value: f64 },
+    Negative { parameter: &'static str, value: f64 },
+    NotPositive { parameter: &'static str, value: f64 },
+    NotAShare { parameter: &'static str, value: f64 },
+    NoSamples,
+    NoParts,
+    InfiniteMean,
+    EmptyRange { min: f64, max: f64 },
+}
+
+impl fmt::Display for DistributionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFinite { parameter, value } => write!(f, "{} must be a finite number, got {}", parameter, value),
+            Self::Negative { parameter, value } => write!(f, "{} must not be negative, got {}", parameter, value),
+            Self::NotPositive { parameter, value } => write!(f, "{} must be above 0, got {}", parameter, value),
+            Self::NotAShare { parameter, value } => write!(f, "{} must be between 0 and 1, got {}", parameter, value),
+            Self::NoSamples => write!(f, "an empirical distribution needs at least one sample"),
+            Self::NoParts => write!(f, "a mixture needs at least one part"),
+            Self::InfiniteMean => write!(f, "can't scale the spread around an infinite mean"),
+            Self::EmptyRange { min, max } => write!(f, "nothing can fall between {} and {}", min, max),
+        }
+    }
+}
+
+impl std::error::Error for DistributionError {}
+
+fn finite(parameter: &'static str, value: f64) -> Result<(), DistributionError> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(DistributionError::NotFinite { parameter, value })
+    }
+}
+
+fn non_negative(parameter: &'static str, value: f64) -> Result<(), DistributionError> {
+    finite(parameter, value)?;
+    if value < 0.0 {
+        return Err(DistributionError::Negative { parameter, value });
+    }
+    Ok(())
+}
+
+fn positive(parameter: &'static str, value: f64) -> Result<(), DistributionError> {
+    finite(parameter, value)?;
+    if value <= 0.0 {
+        return Err(DistributionError::NotPositive { parameter, value });
+    }
+    Ok(())
+}
+
+impl DistributionType {
+    // Checked constructors. The variants can still be built directly, but
+    // these refuse parameters the distribution can't work with.
+    pub fn normal(mean: f64, std_dev: f64) -> Result<Self, DistributionError> {
+        Self::Normal { mean, std_dev }.checked()
+    }
+
+    pub fn log_normal(location: f64, scale: f64) -> Result<Self, DistributionError> {
+        Self::LogNormal { location, scale }.checked()
+    }
+
+    pub fn exponential(rate: f64) -> Result<Self, DistributionError> {
+        Self::Exponential { rate }.checked()
+    }
+
+    pub fn poisson(lambda: f64) -> Result<Self, DistributionError> {
+        Self::Poisson { lambda }.checked()
+    }
+
+    pub fn beta(alpha: f64, beta: f64) -> Result<Self, DistributionError> {
+        Self::Beta { alpha, beta }.checked()
+    }
+
+    pub fn weibull(scale: f64, shape: f64) -> Result<Self, DistributionError> {
+        Self::Weibull { scale, shape }.checked()
+    }
+
+    pub fn pareto(scale: f64, shape: f64) -> Result<Self, DistributionError> {
+        Self::Pareto { scale, shape }.checked()
+    }
+
+    pub fn empirical(samples: Vec<f64>) -> Result<Self, DistributionError> {
+        Self::Empirical { samples }.checked()
+    }
+
+    pub(crate) fn checked(self) -> Result<Self, DistributionError> {
+        self.validate().map(|()| self)
+    }
+
+    // A zero spread is allowed for Normal and LogNormal: tests and
+    // scripted scenarios use them as fixed values
+    pub fn validate(&self) -> Result<(), DistributionError> {
+        match self {
+            Self::Normal { mean, std_dev } => {
+                finite("mean", *mean)?;
+                non_negative("std_dev", *std_dev)
+            }
+            Self::LogNormal { location, scale } => {
+                finite("location", *location)?;
+                non_negative("scale", *scale)
+            }
+            Self::Exponential { rate } => positive("rate", *rate),
+            Self::Poisson { lambda } => positive("lambda", *lambda),
+            Self::Beta { alpha, beta } => {
+                positive("alpha", *alpha)?;
+                positive("beta", *beta)
+            }
+            Self::Weibull { scale, shape } | Self::Pareto { scale, shape } => {
+                positive("scale", *scale)?;
+                positive("shape", *shape)
+            }
+            Self::Empirical { samples } => {
+                if samples.is_empty() {
+                    return Err(DistributionError::NoSamples);
+                }
+                samples.iter().try_for_each(|sample| finite("samples", *sample))
+            }
+            Self::Shifted { base, offset } => {
+                base.validate()?;
+                finite("offset", *offset)
+            }
+            Self::Scaled { base, factor } => {
+                base.validate()?;
+                positive("factor", *factor)?;
+                if !base.mean().is_finite() {
+                    return Err(DistributionError::InfiniteMean);
+                }
+                Ok(())
+            }
+            Self::Clamped { base, min, max } => {
+                base.validate()?;
+                if min.is_nan() || max.is_nan() || min > max {
+                    return Err(DistributionError::EmptyRange { min: *min, max: *max });
+                }
+                Ok(())
+            }
+            Self::Truncated { base, min, max } => {
+                base.validate()?;
+                if min.is_nan() || max.is_nan() || min >= max || base.cdf(*max) <= base.cdf(*min) {
+                    return Err(DistributionError::EmptyRange { min: *min, max: *max });
+                }
+                Ok(())
+            }
+            Self::Mixture { parts } => {
+                if parts.is_empty() {
+                    return Err(DistributionError::NoParts);
+                }
+                for (weight, part) in parts {
+                    non_negative("weight", *weight)?;
+                    part.validate()?;
+                }
+                let total = parts.iter().map(|(weight, _)| weight).sum::<f64>();
+                positive("total weight", total)
+            }
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Normal { .. } => "Normal",
+            Self::LogNormal { .. } => "LogNormal",
+            Self::Exponential { .. } => "Exponential",
+            Self::Poisson { .. } => "Poisson",
+            Self::Beta { .. } => "Beta",
+            Self::Weibull { .. } => "Weibull",
+            Self::Pareto { .. } => "Pareto",
+            Self::Empirical { .. } => "Empirical",
+            Self::Shifted { .. } => "Shifted",
+            Self::Scaled { .. } => "Scaled",
+            Self::Clamped { .. } => "Clamped",
+            Self::Truncated { .. } => "Truncated",
+            Self::Mixture { .. } => "Mixture",
+        }
+    }
+
+    // Draw a value using the caller's RNG so seeded runs stay reproducible.
+    // Parameters are validated when built or loaded, not here on the hot
+    // path; anything rand_distr still refuses gives the analytic mean (or 0).
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let drawn = match self {
+            Self::Normal { mean, std_dev } => Normal::new(*mean, *std_dev).ok().map(|d| d.sample(rng)),
+            Self::LogNormal { location, scale } => LogNormal::new(*location, *scale).ok().map(|d| d.sample(rng)),
+            Self::Exponential { rate } => Exp::new(*rate).ok().map(|d| d.sample(rng)),
+            Self::Poisson { lambda } => Poisson::new(*lambda).ok().map(|d| d.sample(rng)),
+            Self::Beta { alpha, beta } => Beta::new(*alpha, *beta).ok().map(|d| d.sample(rng)),
+            Self::Weibull { scale, shape } => Weibull::new(*scale, *shape).ok().map(|d| d.sample(rng)),
+            Self::Pareto { scale, shape } => Pareto::new(*scale, *shape).ok().map(|d| d.sample(rng)),
+            Self::Empirical { samples } => samples.choose(rng).copied(),
+            Self::Shifted { base, offset } => Some(base.sample(rng) + offset),
+            Self::Scaled { base, factor } => {
+                let mean = base.mean();
+                Some(mean + factor * (base.sample(rng) - mean))
+            }
+            Self::Clamped { base, min, max } => Some(base.sample(rng).clamp(*min, *max)),
+            Self::Truncated { base, min, max } => {
+                // Inverse transform over the part of the base that's kept
+                let p = rng.gen_range(base.cdf(*min)..base.cdf(*max));
+                Some(base.quantile(p).clamp(*min, *max))
+            }
+            Self::Mixture { parts } => {
+                let total: f64 = parts.iter().map(|(weight, _)| weight).sum();
+                let mut pick = rng.gen::<f64>() * total;
+                let (_, part) = parts.iter()
+                    .find(|(weight, _)| {
+                        pick -= weight;
+                        pick < 0.0
+                    })
+                    .unwrap_or(&parts[parts.len() - 1]);
+                Some(part.sample(rng))
+            }
+        };
+        drawn.unwrap_or_else(|| self.fallback())
+    }
+
+    fn fallback(&self) -> f64 {
+        let mean = self.mean();
+        if mean.is_finite() { mean } else { 0.0 }
+    }
+
+    // Expected value. Infinite for Pareto tails too heavy to have one.
+    // Clamped and truncated distributions are integrated numerically.
+    pub fn mean(&self) -> f64 {
+        match self {
+            Self::Normal { mean, .. } => *mean,
+            Self::LogNormal { location, scale } => (location + scale * scale / 2.0).exp(),
+            Self::Exponential { rate } => 1.0 / rate,
+            Self::Poisson { lambda } => *lambda,
+            Self::Beta { alpha, beta } => alpha / (alpha + beta),
+            Self::Weibull { scale, shape } => scale * gamma(1.0 + 1.0 / shape),
+            Self::Pareto { scale, shape } => {
+                if *shape <= 1.0 { f64::INFINITY } else { shape * scale / (shape - 1.0) }
+            }
+            Self::Empirical { samples } => samples.iter().sum::<f64>() / samples.len() as f64,
+            Self::Shifted { base, offset } => base.mean() + offset,
+            Self::Scaled { base, .. } => base.mean(),
+            Self::Clamped { .. } | Self::Truncated { .. } => self.numeric_moments().0,
+            Self::Mixture { parts } => {
+                let total: f64 = parts.iter().map(|(weight, _)| weight).sum();
+                parts.iter().map(|(weight, part)| weight * part.mean()).sum::<f64>() / total
+            }
+        }
+    }
+
+    pub fn variance(&self) -> f64 {
+        match self {
+            Self::Normal { std_dev, .. } => std_dev * std_dev,
+            Self::LogNormal { location, scale } => {
+                let s2 = scale * scale;
+                (s2.exp() - 1.0) * (2.0 * location + s2).exp()
+            }
+            Self::Exponential { rate } => 1.0 / (rate * rate),
+            Self::Poisson { lambda } => *lambda,
+            Self::Beta { alpha, beta } => {
+                let total = alpha + beta;
+                alpha * beta / (total * total * (total + 1.0))
+            }
+            Self::Weibull { scale, shape } => {
+                let first = gamma(1.0 + 1.0 / shape);
+                scale * scale * (gamma(1.0 + 2.0 / shape) - first * first)
+            }
+            Self::Pareto { scale, shape } => {
+                if *shape <= 2.0 {
+                    f64::INFINITY
+                } else {
+                    scale * scale * shape / ((shape - 1.0).powi(2) * (shape - 2.0))
+                }
+            }
+            Self::Empirical { samples } => {
+                let mean = self.mean();
+                samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64
+            }
+            Self::Shifted { base, .. } => base.variance(),
+            Self::Scaled { base, factor } => factor * factor * base.variance(),
+            Self::Clamped { .. } | Self::Truncated { .. } => self.numeric_moments().1,
+            Self::Mixture { parts } => {
+                let total: f64 = parts.iter().map(|(weight, _)| weight).sum();
+                let second_moment = parts.iter()
+                    .map(|(weight, part)| weight * (part.variance() + part.mean().powi(2)))
+                    .sum::<f64>() / total;
+                second_moment - self.mean().powi(2)
+            }
+        }
+    }
+
+    // Mean and variance from the quantile function, for shapes without a closed form
+    fn numeric_moments(&self) -> (f64, f64) {
+        let values: Vec<f64> = (0..MOMENT_STEPS)
+            .map(|step| self.quantile((step as f64 + 0.5) / MOMENT_STEPS as f64))
+            .collect();
+        let mean = values.iter().sum::<f64>() / MOMENT_STEPS as f64;
+        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / MOMENT_STEPS as f64;
+        (mean, variance)
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    // Value below which a share `p` of draws fall (p is clamped to 0-1).
+    // Empirical distributions use the nearest-rank sample.
+    pub fn quantile(&self, p: f64) -> f64 {
+        let p = p.clamp(0.0, 1.0);
+        match self {
+            Self::Normal { mean, std_dev } => normal_quantile(*mean, *std_dev, p),
+            Self::LogNormal { location, scale } => normal_quantile(*location, *scale, p).exp(),
+            Self::Exponential { rate } => -(1.0 - p).ln() / rate,
+            Self::Poisson { lambda } => poisson_quantile(*lambda, p),
+            Self::Beta { alpha, beta } => beta_quantile(*alpha, *beta, p),
+            Self::Weibull { scale, shape } => scale * (-(1.0 - p).ln()).powf(1.0 / shape),
+            Self::Pareto { scale, shape } => scale * (1.0 - p).powf(-1.0 / shape),
+            Self::Empirical { samples } => {
+                let mut sorted = samples.clone();
+                sorted.sort_by(f64::total_cmp);
+                percentile(&sorted, p)
+            }
+            Self::Shifted { base, offset } => base.quantile(p) + offset,
+            Self::Scaled { base, factor } => {
+                let mean = base.mean();
+                mean + factor * (base.quantile(p) - mean)
+            }
+            Self::Clamped { base, min, max } => base.quantile(p).clamp(*min, *max),
+            Self::Truncated { base, min, max } => {
+                let (low, high) = (base.cdf(*min), base.cdf(*max));
+                base.quantile(low + p * (high - low)).clamp(*min, *max)
+            }
+            Self::Mixture { parts } => mixture_quantile(self, parts, p),
+        }
+    }
+
+    // Share of draws at or below x
+    pub fn cdf(&self, x: f64) -> f64 {
+        match self {
+            Self::Normal { mean, std_dev } => normal_cdf(*mean, *std_dev, x),
+            Self::LogNormal { location, scale } => {
+                if x <= 0.0 { 0.0 } else { normal_cdf(*location, *scale, x.ln()) }
+            }
+            Self::Exponential { rate } => if x < 0.0 { 0.0 } else { 1.0 - (-rate * x).exp() },
+            Self::Poisson { lambda } => {
+                match statrs::distribution::Poisson::new(*lambda) {
+                    Ok(dist) if x >= 0.0 => dist.cdf(x.floor() as u64),
+                    _ => 0.0,
+                }
+            }
+            Self::Beta { alpha, beta } => {
+                match statrs::distribution::Beta::new(*alpha, *beta) {
+                    Ok(dist) => dist.cdf(x.clamp(0.0, 1.0)),
+                    Err(_) => 0.0,
+                }
+            }
+            Self::Weibull { scale, shape } => {
+                if x < 0.0 { 0.0 } else { 1.0 - (-(x / scale).powf(*shape)).exp() }
+            }
+            Self::Pareto { scale, shape } => if x < *scale { 0.0 } else { 1.0 - (scale / x).powf(*shape) },
+            Self::Empirical { samples } => {
+                samples.iter().filter(|&&sample| sample <= x).count() as f64 / samples.len() as f64
+            }
+            Self::Shifted { base, offset } => base.cdf(x - offset),
+            Self::Scaled { base, factor } => {
+                let mean = base.mean();
+                base.cdf(mean + (x - mean) / factor)
+            }
+            Self::Clamped { base, min, max } => {
+                if x < *min { 0.0 } else if x >= *max { 1.0 } else { base.cdf(x) }
+            }
+            Self::Truncated { base, min, max } => {
+                let (low, high) = (base.cdf(*min), base.cdf(*max));
+                ((base.cdf(x.clamp(*min, *max)) - low) / (high - low)).clamp(0.0, 1.0)
+            }
+            Self::Mixture { parts } => {
+                let total: f64 = parts.iter().map(|(weight, _)| weight).sum();
+                parts.iter().map(|(weight, part)| weight * part.cdf(x)).sum::<f64>() / total
+            }
+        }
+    }
+
+    // Central tendency parameter cards can adjust (mean for Normal, log-space
+    // location for LogNormal, rate for Poisson, scale for Weibull and Pareto).
+    // None where no single parameter moves the centre on its own.
+    pub fn location_mut(&mut self) -> Option<&mut f64> {
+        match self {
+            Self::Normal { mean, .. } => Some(mean),
+            Self::LogNormal { location, .. } => Some(location),
+            Self::Poisson { lambda } => Some(lambda),
+            Self::Weibull { scale, .. } | Self::Pareto { scale, .. } => Some(scale),
+            Self::Shifted { base, .. } | Self::Scaled { base, .. }
+            | Self::Clamped { base, .. } | Self::Truncated { base, .. } => base.location_mut(),
+            Self::Exponential { .. } | Self::Beta { .. } | Self::Empirical { .. } | Self::Mixture { .. } => None,
+        }
+    }
+
+    // Range the location parameter must stay in for the distribution to stay valid
+    pub fn location_bounds(&self) -> (f64, f64) {
+        match self {
+            Self::LogNormal { .. } => (f64::NEG_INFINITY, f64::INFINITY),
+            // Rates and scales must stay above zero to remain valid
+            Self::Poisson { .. } | Self::Weibull { .. } | Self::Pareto { .. } => (f64::MIN_POSITIVE, f64::INFINITY),
+            Self::Shifted { base, .. } | Self::Scaled { base, .. }
+            | Self::Clamped { base, .. } | Self::Truncated { base, .. } => base.location_bounds(),
+            _ => (0.0, f64::INFINITY),
+        }
+    }
+
+    // Spread parameter (std_dev for Normal, log-space scale for LogNormal).
+    // None for distributions whose spread is tied to their centre.
+    pub fn spread_mut(&mut self) -> Option<&mut f64> {
+        match self {
+            Self::Normal { std_dev, .. } => Some(std_dev),
+            Self::LogNormal { scale, .. } => Some(scale),
+            Self::Scaled { factor, .. } => Some(factor),
+            Self::Shifted { base, .. } | Self::Clamped { base, .. } | Self::Truncated { base, .. } => base.spread_mut(),
+            _ => None,
+        }
+    }
+
+    // A scaled distribution's factor must stay above zero; plain spreads may reach it
+    pub fn spread_bounds(&self) -> (f64, f64) {
+        match self {
+            Self::Scaled { .. } => (f64::MIN_POSITIVE, f64::INFINITY),
+            Self::Shifted { base, .. } | Self::Clamped { base, .. } | Self::Truncated { base, .. } => base.spread_bounds(),
+            _ => (0.0, f64::INFINITY),
+        }
+    }
+}
+
+impl Default for DistributionType {
+    fn default() -> Self {
+        Self::Normal { mean: 0.0, std_dev: 1.0 }
+    }
+}
+
+fn normal_cdf(mean: f64, std_dev: f64, x: f64) -> f64 {
+    if std_dev == 0.0 {
+        return if x >= mean { 1.0 } else { 0.0 };
+    }
+    0.5 * (1.0 + erf((x - mean) / (std_dev * SQRT_2)))
+}
+
+fn normal_quantile(mean: f64, std_dev: f64, p: f64) -> f64 {
+    if std_dev == 0.0 {
+        return mean;
+    }
+    mean + std_dev * SQRT_2 * erf_inv(2.0 * p - 1.0)
+}
+
+// Smallest count whose cumulative probability reaches p
+fn poisson_quantile(lambda: f64, p: f64) -> f64 {
+    let Ok(dist) = statrs::distribution::Poisson::new(lambda) else {
+        return f64::NAN;
+    };
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+    let mut k = 0;
+    while dist.cdf(k) < p {
+        k += 1;
+    }
+    k as f64
+}
+
+fn beta_quantile(alpha: f64, beta: f64, p: f64) -> f64 {
+    let Ok(dist) = statrs::distribution::Beta::new(alpha, beta) else {
+        return f64::NAN;
+    };
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..64 {
+        let mid = (low + high) / 2.0;
+        if dist.cdf(mid) < p {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (low + high) / 2.0
+}
+
+// No closed form: bisect the mixture's cdf between the parts' own quantiles
+fn mixture_quantile(mixture: &DistributionType, parts: &[(f64, DistributionType)], p: f64) -> f64 {
+    let quantiles = parts.iter()
+        .filter(|(weight, _)| *weight > 0.0)
+        .map(|(_, part)| part.quantile(p));
+    let (mut low, mut high) = quantiles.fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), q| (low.min(q), high.max(q)));
+    if !low.is_finite() || !high.is_finite() {
+        return if p < 0.5 { low } else { high };
+    }
+    for _ in 0..100 {
+        let mid = (low + high) / 2.0;
+        if mixture.cdf(mid) < p {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    high
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::SimRng;
+
+    fn close(a: f64, b: f64, tolerance: f64) -> bool {
+        (a - b).abs() <= tolerance * b.abs().max(1.0)
+    }
+
+    fn all_kinds() -> Vec<DistributionType> {
+        vec![
+            DistributionType::normal(10.0, 2.0).unwrap(),
+            DistributionType::log_normal(1.0, 0.5).unwrap(),
+            DistributionType::exponential(0.5).unwrap(),
+            DistributionType::poisson(4.0).unwrap(),
+            DistributionType::beta(2.0, 5.0).unwrap(),
+            DistributionType::weibull(3.0, 1.5).unwrap(),
+            DistributionType::pareto(1.0, 6.0).unwrap(),
+            DistributionType::empirical(vec![1.0, 2.0, 2.0, 7.0]).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_invalid_parameters_are_rejected() {
+        assert_eq!(
+            DistributionType::log_normal(-3.0, -0.5),
+            Err(DistributionError::Negative { parameter: "scale", value: -0.5 })
+        );
+        assert_eq!(
+            DistributionType::exponential(0.0),
+            Err(DistributionError::NotPositive { parameter: "rate", value: 0.0 })
+        );
+        assert!(matches!(DistributionType::normal(f64::NAN, 1.0), Err(DistributionError::NotFinite { .. })));
+        assert!(matches!(DistributionType::weibull(1.0, -2.0), Err(DistributionError::NotPositive { parameter: "shape", .. })));
+        assert_eq!(DistributionType::empirical(vec![]), Err(DistributionError::NoSamples));
+        assert_eq!(
+            DistributionType::beta(0.0, 1.0).unwrap_err().to_string(),
+            "alpha must be above 0, got 0"
+        );
+        // Fixed values are fine
+        assert!(DistributionType::normal(5.0, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_analytic_moments_match_sampling() {
+        let mut rng = SimRng::new(11);
+        for dist in all_kinds() {
+            let samples: Vec<f64> = (0..40_000).map(|_| dist.sample(&mut rng)).collect();
+            let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+            let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+            assert!(close(mean, dist.mean(), 0.03), "{}: mean {} vs {}", dist.name(), mean, dist.mean());
+            assert!(close(variance, dist.variance(), 0.1), "{}: variance {} vs {}", dist.name(), variance, dist.variance());
+
+            let mut sorted = samples;
+            sorted.sort_by(f64::total_cmp);
+            for p in [0.1, 0.5, 0.9] {
+                let empirical = percentile(&sorted, p);
+                assert!(close(empirical, dist.quantile(p), 0.05),
+                    "{}: q{} {} vs {}", dist.name(), p, empirical, dist.quantile(p));
+            }
+        }
+    }
+
+    #[test]
+    fn test_quantiles() {
+        assert!(close(DistributionType::normal(0.0, 1.0).unwrap().quantile(0.975), 1.959964, 1e-5));
+        assert_eq!(DistributionType::normal(3.0, 0.0).unwrap().quantile(0.99), 3.0);
+        assert!(close(DistributionType::exponential(2.0).unwrap().quantile(0.5), 2f64.ln() / 2.0, 1e-12));
+        assert_eq!(DistributionType::poisson(1.0).unwrap().quantile(0.5), 1.0);
+        assert!(close(DistributionType::beta(1.0, 1.0).unwrap().quantile(0.25), 0.25, 1e-9));
+        assert_eq!(DistributionType::empirical(vec![5.0, 1.0, 3.0]).unwrap().quantile(0.5), 3.0);
+        assert!(DistributionType::pareto(1.0, 0.8).unwrap().mean().is_infinite());
+    }
+
+    #[test]
+    fn test_serialized_names() {
+        let json = serde_json::to_string(&DistributionType::Weibull { scale: 2.0, shape: 1.5 }).unwrap();
+        assert_eq!(json, r#"{"Weibull":{"scale":2.0,"shape":1.5}}"#);
+        let dist: DistributionType = serde_json::from_str(r#"{"Empirical":{"samples":[1.0,4.0]}}"#).unwrap();
+        assert_eq!(dist.mean(), 2.5);
+    }
+}
+
+// Easter egg: "All models are wrong. Pareto's are wrong with a heavier tail 🐉"
@@ -0,0 +1,236 @@
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+use petgraph::Direction;
+use rand::Rng;
+use std::collections::{HashMap, HashSet, VecDeque};
+use super::system_graph::{SystemGraph, SystemNode, SystemEdge};
+
+// Simulated seconds a healthy, debt-free component needs to come back after failing
+pub const BASE_RECOVERY_SECS: f64 = 1.0;
+
+// Slack for float drift when counting down recovery in tick-sized steps
+const RECOVERY_EPSILON: f64 = 1e-9;
+
+// What went wrong during one tick
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CascadeReport {
+    pub failed_nodes: Vec<String>,  // Failed on their own this tick
+    pub failed_links: Vec<(String, String)>,  // Connections that broke this tick
+    pub down: Vec<String>,  // Every unavailable node, including dependents taken down by the cascade
+}
+
+impl CascadeReport {
+    pub fn is_down(&self, name: &str) -> bool {
+        self.down.iter().any(|n| n == name)
+    }
+}
+
+// Outages in progress, with the simulated seconds left until each recovers.
+//
+// Edges point from a component to what it depends on, so an outage travels
+// against the edge direction: when `database` is down, everything with an
+// edge into `database` is down with it, and so on up the graph.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Outages {
+    nodes: HashMap<String, f64>,
+    links: HashMap<(String, String), f64>,
+}
+
+impl Outages {
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.links.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.links.clear();
+    }
+
+    // Advance outages by `dt` simulated seconds: recover what has been down long
+    // enough, roll new node and link failures, then cascade to dependents
+    pub fn step<R: Rng + ?Sized>(&mut self, system: &SystemGraph, dt: f64, rng: &mut R) -> CascadeReport {
+        self.nodes.retain(|_, remaining| { *remaining -= dt; *remaining > RECOVERY_EPSILON });
+        self.links.retain(|_, remaining| { *remaining -= dt; *remaining > RECOVERY_EPSILON });
+
+        let mut report = CascadeReport::default();
+
+        for node in system.graph.node_weights() {
+            let rate = node_failure_rate(node, rng);
+            if rng.gen::<f64>() < failure_probability(rate, dt) && !self.nodes.contains_key(&node.name) {
+                self.nodes.insert(node.name.clone(), recovery_secs(node.tech_debt));
+                report.failed_nodes.push(node.name.clone());
+            }
+        }
+
+        for edge in system.graph.edge_references() {
+            let key = (system.graph[edge.source()].name.clone(), system.graph[edge.target()].name.clone());
+            let rate = edge_failure_rate(edge.weight(), rng);
+            if rng.gen::<f64>() < failure_probability(rate, dt) && !self.links.contains_key(&key) {
+                let debt = system.graph[edge.source()].tech_debt.max(system.graph[edge.target()].tech_debt);
+                self.links.insert(key.clone(), recovery_secs(debt));
+                report.failed_links.push(key);
+            }
+        }
+
+        report.down = self.cascade(system);
+        report
+    }
+
+    // Every node that can't serve: down itself, cut off from a dependency by a
+    // broken link, or depending (transitively) on something that is
+    pub fn cascade(&self, system: &SystemGraph) -> Vec<String> {
+        let mut down = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        let roots = self.nodes.keys().map(String::as_str)
+            .chain(self.links.keys().map(|(from, _)| from.as_str()));
+        for name in roots {
+            if let Some(&idx) = system.node_indices.get(name) {
+                if down.insert(idx) {
+                    queue.push_back(idx);
+                }
+            }
+        }
+
+        while let Some(idx) = queue.pop_front() {
+            for dependent in system.graph.neighbors_directed(idx, Direction::Incoming) {
+                if down.insert(dependent) {
+                    queue.push_back(dependent);
+                }
+            }
+        }
+
+        // Report in graph order so seeded runs produce identical reports
+        system.graph.node_indices()
+            .filter(|idx| down.contains(idx))
+            .map(|idx| system.graph[idx].name.clone())
+            .collect()
+    }
+}
+
+// Failures per simulated second. Sampled from the node's distribution and
+// made worse by poor health: a node at 0% health fails twice as often.
+pub fn node_failure_rate<R: Rng + ?Sized>(node: &SystemNode, rng: &mut R) -> f64 {
+    let health_factor = 1.0 + (100.0 - node.health.clamp(0.0, 100.0)) / 100.0;
+    node.failure_rate.sample(rng).max(0.0) * health_factor
+}
+
+// Failures per simulated second for a connection. Reliability is the chance the
+// link survives one second on its own, on top of its sampled failure rate.
+pub fn edge_failure_rate<R: Rng + ?Sized>(edge: &SystemEdge, rng: &mut R) -> f64 {
+    let reliability = edge.reliability.clamp(f64::MIN_POSITIVE, 1.0);
+    edge.failure_rate.sample(rng).max(0.0) - reliability.ln()
+}
+
+// Chance of at least one failure in `dt` seconds at `rate` failures per second.
+// Splitting a sprint into more ticks doesn't change how often things break.
+pub fn failure_probability(rate: f64, dt: f64) -> f64 {
+    1.0 - (-rate.max(0.0) * dt).exp()
+}
+
+// Tech debt makes recovery slower: 100% debt triples the time to recover
+pub fn recovery_secs(tech_debt: f64) -> f64 {
+    BASE_RECOVERY_SECS * (1.0 + tech_debt.clamp(0.0, 100.0) / 50.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::DistributionType;
+    use crate::resources::SimRng;
+
+    const NEVER: DistributionType = DistributionType::Normal { mean: 0.0, std_dev: 0.0 };
+    const ALWAYS: DistributionType = DistributionType::Normal { mean: 1.0e6, std_dev: 0.0 };
+
+    fn node(name: &str, failure_rate: DistributionType) -> SystemNode {
+        SystemNode { name: name.into(), failure_rate, ..Default::default() }
+    }
+
+    fn edge(failure_rate: DistributionType) -> SystemEdge {
+        SystemEdge { failure_rate, ..Default::default() }
+    }
+
+    // gateway -> core -> {db, cache}, with an independent 'batch' job also using db
+    fn create_system(db_rate: DistributionType) -> SystemGraph {
+        let mut system = SystemGraph::new();
+        system.add_node(node("gateway", NEVER));
+        system.add_node(node("core", NEVER));
+        system.add_node(node("db", db_rate));
+        system.add_node(node("cache", NEVER));
+        system.add_node(node("batch", NEVER));
+        system.add_edge("gateway", "core", edge(NEVER));
+        system.add_edge("core", "db", edge(NEVER));
+        system.add_edge("core", "cache", edge(NEVER));
+        system.add_edge("batch", "db", edge(NEVER));
+        system
+    }
+
+    #[test]
+    fn test_failure_cascades_to_dependents() {
+        let system = create_system(ALWAYS);
+        let mut outages = Outages::default();
+        let report = outages.step(&system, 0.1, &mut SimRng::new(1));
+
+        assert_eq!(report.failed_nodes, vec!["db".to_string()]);
+        assert_eq!(report.down, vec!["gateway", "core", "db", "batch"]);
+        assert!(!report.is_down("cache"));
+    }
+
+    #[test]
+    fn test_reliable_system_stays_up() {
+        let system = create_system(NEVER);
+        let mut outages = Outages::default();
+        let mut rng = SimRng::new(2);
+        for _ in 0..100 {
+            assert_eq!(outages.step(&system, 0.1, &mut rng), CascadeReport::default());
+        }
+        assert!(outages.is_empty());
+    }
+
+    #[test]
+    fn test_outage_recovers_after_recovery_time() {
+        let mut system = create_system(ALWAYS);
+        let mut outages = Outages::default();
+        let mut rng = SimRng::new(3);
+        outages.step(&system, 0.1, &mut rng);
+
+        // Stop new failures; db needs BASE_RECOVERY_SECS to come back
+        system.node_mut("db").unwrap().failure_rate = NEVER;
+        let ticks = (0..100)
+            .take_while(|_| outages.step(&system, 0.1, &mut rng).is_down("db"))
+            .count();
+        assert_eq!(ticks + 1, (BASE_RECOVERY_SECS / 0.1).round() as usize);
+        assert!(outages.is_empty());
+    }
+
+    #[test]
+    fn test_broken_link_takes_down_caller_only() {
+        let mut system = create_system(NEVER);
+        system.remove_edge("core", "cache").unwrap();
+        system.add_edge("core", "cache", edge(ALWAYS));
+
+        let report = Outages::default().step(&system, 0.1, &mut SimRng::new(4));
+        assert_eq!(report.failed_links, vec![("core".to_string(), "cache".to_string())]);
+        assert_eq!(report.down, vec!["gateway", "core"]);
+    }
+
+    #[test]
+    fn test_failure_probability_independent_of_tick_size() {
+        let rate = 0.3;
+        let one_tick = failure_probability(rate, 1.0);
+        let survive_ten = (1.0 - failure_probability(rate, 0.1)).powi(10);
+        assert!((one_tick - (1.0 - survive_ten)).abs() < 1e-12);
+        assert_eq!(failure_probability(-1.0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_unhealthy_nodes_fail_more_often() {
+        let fixed = DistributionType::Normal { mean: 0.5, std_dev: 0.0 };
+        let mut rng = SimRng::new(5);
+        let healthy = node("a", fixed.clone());
+        let sick = SystemNode { health: 0.0, ..node("b", fixed) };
+        assert_eq!(node_failure_rate(&healthy, &mut rng), 0.5);
+        assert_eq!(node_failure_rate(&sick, &mut rng), 1.0);
+    }
+}
+
+// Easter egg: "Everything fails, all the time. — Werner Vogels, probably during a cascade 🌊"
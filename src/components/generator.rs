@@ -0,0 +1,316 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::{Serialize, Deserialize};
+use super::system_graph::{SystemGraph, SystemNode, SystemEdge};
+use super::distribution::DistributionType;
+use super::critical_path::REVENUE_ATTRIBUTE;
+
+// Name under which the generator shows up next to the architecture templates
+pub const GENERATED_ARCHITECTURE: &str = "Generated";
+
+// Extra debt and complexity carried by legacy components
+const LEGACY_DEBT: f64 = 20.0;
+const LEGACY_COMPLEXITY: f64 = 1.5;
+
+// Shape of a generated architecture. Requests enter at the gateways in the
+// first layer and work their way down to storage and caches in the last.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeneratorParams {
+    pub nodes: usize,
+    pub depth: usize,  // Layers from gateways down to storage
+    pub fan_out: usize,  // Most dependencies a node has on the layer below
+    pub legacy_share: f64,  // Fraction of nodes that are legacy
+    pub debt: DistributionType,  // Starting tech debt, clamped to 0-100
+}
+
+impl Default for GeneratorParams {
+    fn default() -> Self {
+        Self {
+            nodes: 10,
+            depth: 4,
+            fan_out: 2,
+            legacy_share: 0.2,
+            debt: DistributionType::Normal { mean: 20.0, std_dev: 10.0 },
+        }
+    }
+}
+
+// Defaults for each kind of component, in line with the built-in templates
+struct Profile {
+    node_type: &'static str,
+    complexity: u32,
+    contagion_risk: f64,
+    operating_cost: f64,
+    latency: (f64, f64),
+    failure_location: f64,
+    defect_rate: f64,
+}
+
+const GATEWAY: Profile = Profile {
+    node_type: "gateway", complexity: 8, contagion_risk: 0.4, operating_cost: 200.0,
+    latency: (50.0, 10.0), failure_location: -4.0, defect_rate: 0.1,
+};
+const SERVICE: Profile = Profile {
+    node_type: "service", complexity: 6, contagion_risk: 0.3, operating_cost: 150.0,
+    latency: (80.0, 20.0), failure_location: -4.0, defect_rate: 0.12,
+};
+const MESSAGING: Profile = Profile {
+    node_type: "messaging", complexity: 10, contagion_risk: 0.6, operating_cost: 300.0,
+    latency: (30.0, 10.0), failure_location: -5.0, defect_rate: 0.1,
+};
+const STORAGE: Profile = Profile {
+    node_type: "storage", complexity: 5, contagion_risk: 0.3, operating_cost: 250.0,
+    latency: (50.0, 10.0), failure_location: -4.5, defect_rate: 0.08,
+};
+const CACHE: Profile = Profile {
+    node_type: "cache", complexity: 3, contagion_risk: 0.2, operating_cost: 100.0,
+    latency: (5.0, 1.0), failure_location: -3.0, defect_rate: 0.05,
+};
+
+// How many nodes go in each layer, first layer first
+fn layer_sizes(nodes: usize, depth: usize) -> Vec<usize> {
+    let nodes = nodes.max(2);
+    let gateways = (nodes / 12).max(1);
+    let data = (nodes / 4).max(1);
+    let services = nodes.saturating_sub(gateways + data);
+    let middle = depth.max(2).saturating_sub(2).min(services);
+    if middle == 0 {
+        return vec![gateways, nodes - gateways];
+    }
+
+    let mut sizes = vec![gateways];
+    sizes.extend((0..middle).map(|layer| services / middle + usize::from(layer < services % middle)));
+    sizes.push(data);
+    sizes
+}
+
+fn build_node<R: Rng + ?Sized>(
+    name: String,
+    profile: &Profile,
+    legacy: bool,
+    params: &GeneratorParams,
+    rng: &mut R,
+) -> SystemNode {
+    let mut tech_debt = params.debt.sample(rng);
+    let mut complexity = profile.complexity as f64;
+    let mut attributes = Vec::new();
+    if legacy {
+        tech_debt += LEGACY_DEBT;
+        complexity *= LEGACY_COMPLEXITY;
+        attributes.push("legacy".to_string());
+    }
+    SystemNode {
+        name,
+        node_type: profile.node_type.into(),
+        health: 100.0,
+        tech_debt: tech_debt.clamp(0.0, 100.0),
+        complexity: complexity.round() as u32,
+        contagion_risk: profile.contagion_risk,
+        operating_cost: profile.operating_cost,
+        critical_path: false,
+        attributes,
+        latency: DistributionType::Normal { mean: profile.latency.0, std_dev: profile.latency.1 },
+        failure_rate: DistributionType::LogNormal { location: profile.failure_location, scale: 0.3 },
+        defect_rate: profile.defect_rate,
+    }
+}
+
+fn build_edge(from: &SystemNode, to: &SystemNode) -> SystemEdge {
+    let legacy = [from, to].iter().any(|node| node.attributes.iter().any(|a| a == "legacy"));
+    SystemEdge {
+        name: format!("{}_to_{}", from.name, to.name),
+        reliability: 0.999,
+        latency: DistributionType::Normal { mean: 15.0, std_dev: 5.0 },
+        tech_debt_spread: if legacy { 0.3 } else { 0.2 },
+        bandwidth: 1000.0,
+        failure_rate: DistributionType::LogNormal { location: -5.0, scale: 0.2 },
+    }
+}
+
+// Build a layered architecture. The same params and RNG state always give the same graph.
+pub fn generate_architecture<R: Rng + ?Sized>(params: &GeneratorParams, rng: &mut R) -> SystemGraph {
+    let mut system = SystemGraph::new();
+    system.in_bulk(|system| build_layers(system, params, rng));
+    system
+}
+
+fn build_layers<R: Rng + ?Sized>(system: &mut SystemGraph, params: &GeneratorParams, rng: &mut R) {
+    let sizes = layer_sizes(params.nodes, params.depth);
+    let last = sizes.len() - 1;
+    let total: usize = sizes.iter().sum();
+
+    // Pick which nodes are legacy up front so the share is exact
+    let legacy_count = (params.legacy_share.clamp(0.0, 1.0) * total as f64).round() as usize;
+    let mut legacy = vec![false; total];
+    legacy.iter_mut().take(legacy_count).for_each(|flag| *flag = true);
+    legacy.shuffle(rng);
+
+    let mut layers: Vec<Vec<String>> = Vec::new();
+    let mut counts = std::collections::HashMap::new();
+    let mut index = 0;
+    for (layer, size) in sizes.iter().enumerate() {
+        let mut names = Vec::new();
+        for position in 0..*size {
+            let profile = match layer {
+                0 => &GATEWAY,
+                _ if layer == last && sizes.len() > 2 && position % 3 == 2 => &CACHE,
+                _ if layer == last && sizes.len() > 2 => &STORAGE,
+                _ if layer == last => &SERVICE,
+                // One message bus in the middle of larger systems
+                _ if layer == sizes.len() / 2 && position == 0 && total >= 8 => &MESSAGING,
+                _ => &SERVICE,
+            };
+            let count = counts.entry(profile.node_type).or_insert(0);
+            *count += 1;
+            let name = format!("{}_{}", profile.node_type, count);
+            system.add_node(build_node(name.clone(), profile, legacy[index], params, rng));
+            names.push(name);
+            index += 1;
+        }
+        layers.push(names);
+    }
+
+    // Wire each layer to the next: random dependencies first, then make sure
+    // nothing below the gateways is left unreachable
+    let fan_out = params.fan_out.max(1);
+    for pair in layers.windows(2) {
+        let (upper, lower) = (&pair[0], &pair[1]);
+        let mut reached = vec![false; lower.len()];
+        for from in upper {
+            let count = rng.gen_range(1..=fan_out.min(lower.len()));
+            let mut targets: Vec<usize> = (0..lower.len()).collect();
+            targets.shuffle(rng);
+            for &target in targets.iter().take(count) {
+                connect(system, from, &lower[target]);
+                reached[target] = true;
+            }
+        }
+        for (target, _) in reached.iter().enumerate().filter(|(_, reached)| !**reached) {
+            // Lean on whichever caller has the fewest dependencies so far
+            let from = upper.iter()
+                .min_by_key(|name| system.graph.neighbors(system.node_indices[name.as_str()]).count())
+                .expect("layers are never empty");
+            connect(system, from, &lower[target]);
+        }
+    }
+
+    mark_critical_path(system, &layers);
+}
+
+fn connect(system: &mut SystemGraph, from: &str, to: &str) {
+    let edge = build_edge(system.node(from).expect("generated node"), system.node(to).expect("generated node"));
+    system.add_edge(from, to, edge);
+}
+
+// Where paying customers end up: from the first gateway, always step to the
+// dependency with the most behind it, preferring ones that end up at storage.
+// The critical path is then everything on the way there.
+fn mark_critical_path(system: &mut SystemGraph, layers: &[Vec<String>]) {
+    let Some(mut current) = layers.first().and_then(|gateways| gateways.first()).cloned() else {
+        return;
+    };
+    loop {
+        let idx = system.node_indices[&current];
+        let next = system.graph.neighbors(idx)
+            .max_by_key(|child| {
+                let (reaches_storage, downstream) = downstream(system, *child);
+                (reaches_storage, downstream, std::cmp::Reverse(child.index()))
+            });
+        match next {
+            Some(child) => current = system.graph[child].name.clone(),
+            None => break,
+        }
+    }
+    system.node_mut(&current).expect("generated node").attributes.push(REVENUE_ATTRIBUTE.into());
+}
+
+// Whether storage is reachable from `start`, and how many nodes are
+fn downstream(system: &SystemGraph, start: petgraph::stable_graph::NodeIndex) -> (bool, usize) {
+    let mut dfs = petgraph::visit::Dfs::new(&system.graph, start);
+    let mut reaches_storage = false;
+    let mut count = 0;
+    while let Some(idx) = dfs.next(&system.graph) {
+        reaches_storage |= system.graph[idx].node_type == STORAGE.node_type;
+        count += 1;
+    }
+    (reaches_storage, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::algo::is_cyclic_directed;
+    use petgraph::Direction;
+    use crate::resources::SimRng;
+
+    fn generate(params: &GeneratorParams, seed: u64) -> SystemGraph {
+        generate_architecture(params, &mut SimRng::new(seed))
+    }
+
+    #[test]
+    fn test_same_seed_same_architecture() {
+        let params = GeneratorParams { nodes: 25, ..Default::default() };
+        let a = serde_json::to_string(&generate(&params, 3)).unwrap();
+        let b = serde_json::to_string(&generate(&params, 3)).unwrap();
+        let c = serde_json::to_string(&generate(&params, 4)).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_layers_and_vocabulary() {
+        assert_eq!(layer_sizes(10, 4), vec![1, 4, 3, 2]);
+        assert_eq!(layer_sizes(3, 6), vec![1, 1, 1]);
+        assert_eq!(layer_sizes(2, 4), vec![1, 1]);
+
+        let system = generate(&GeneratorParams { nodes: 40, depth: 5, ..Default::default() }, 9);
+        assert_eq!(system.graph.node_count(), 40);
+        let vocabulary = ["gateway", "service", "storage", "cache", "messaging"];
+        for node in system.graph.node_weights() {
+            assert!(vocabulary.contains(&node.node_type.as_str()), "unexpected {}", node.node_type);
+            assert!((0.0..=100.0).contains(&node.tech_debt));
+        }
+        for node_type in vocabulary {
+            assert!(system.graph.node_weights().any(|node| node.node_type == node_type), "no {}", node_type);
+        }
+    }
+
+    #[test]
+    fn test_every_node_is_reachable_from_a_gateway() {
+        for seed in 0..20 {
+            let params = GeneratorParams { nodes: 30, depth: 5, fan_out: 3, ..Default::default() };
+            let system = generate(&params, seed);
+            assert!(!is_cyclic_directed(&system.graph));
+            for idx in system.graph.node_indices() {
+                let node = &system.graph[idx];
+                let callers = system.graph.neighbors_directed(idx, Direction::Incoming).count();
+                assert_eq!(callers == 0, node.node_type == "gateway", "{} has {} callers", node.name, callers);
+            }
+        }
+    }
+
+    #[test]
+    fn test_critical_path_runs_gateway_to_storage() {
+        let system = generate(&GeneratorParams { nodes: 20, ..Default::default() }, 5);
+        let critical: Vec<&SystemNode> = system.graph.node_weights().filter(|n| n.critical_path).collect();
+        assert!(critical.len() >= 3);
+        assert!(critical.iter().any(|n| n.node_type == "gateway"));
+        assert!(critical.iter().any(|n| n.node_type == "storage"));
+    }
+
+    #[test]
+    fn test_legacy_share_is_exact() {
+        let params = GeneratorParams { nodes: 20, legacy_share: 0.25, ..Default::default() };
+        let system = generate(&params, 1);
+        let legacy: Vec<&SystemNode> = system.graph.node_weights()
+            .filter(|n| n.attributes.iter().any(|a| a == "legacy"))
+            .collect();
+        assert_eq!(legacy.len(), 5);
+
+        let clean = generate(&GeneratorParams { legacy_share: 0.0, ..params }, 1);
+        assert!(clean.graph.node_weights().all(|n| n.attributes.iter().all(|a| a == REVENUE_ATTRIBUTE)));
+    }
+}
+
+// Easter egg: "Procedurally generated, like most architecture diagrams in slide decks 🎲"
@@ -0,0 +1,363 @@
+use petgraph::stable_graph::EdgeIndex;
+use petgraph::visit::EdgeRef;
+use serde::{Serialize, Deserialize};
+use super::system_graph::{SystemGraph, SystemNode, SystemEdge, GraphError};
+
+// Numeric node fields a diff tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NodeMetric {
+    Health,
+    TechDebt,
+    OperatingCost,
+    ContagionRisk,
+    DefectRate,
+}
+
+impl NodeMetric {
+    pub const ALL: [NodeMetric; 5] = [
+        Self::Health, Self::TechDebt, Self::OperatingCost, Self::ContagionRisk, Self::DefectRate,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Health => "health",
+            Self::TechDebt => "tech debt",
+            Self::OperatingCost => "operating cost",
+            Self::ContagionRisk => "contagion risk",
+            Self::DefectRate => "defect rate",
+        }
+    }
+
+    pub fn get(&self, node: &SystemNode) -> f64 {
+        match self {
+            Self::Health => node.health,
+            Self::TechDebt => node.tech_debt,
+            Self::OperatingCost => node.operating_cost,
+            Self::ContagionRisk => node.contagion_risk,
+            Self::DefectRate => node.defect_rate,
+        }
+    }
+
+    pub fn set(&self, node: &mut SystemNode, value: f64) {
+        match self {
+            Self::Health => node.health = value,
+            Self::TechDebt => node.tech_debt = value,
+            Self::OperatingCost => node.operating_cost = value,
+            Self::ContagionRisk => node.contagion_risk = value,
+            Self::DefectRate => node.defect_rate = value,
+        }
+    }
+
+    fn strip(node: &SystemNode) -> SystemNode {
+        let mut stripped = node.clone();
+        for metric in Self::ALL {
+            metric.set(&mut stripped, 0.0);
+        }
+        stripped
+    }
+}
+
+// Numeric edge fields a diff tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EdgeMetric {
+    Reliability,
+    TechDebtSpread,
+    Bandwidth,
+}
+
+impl EdgeMetric {
+    pub const ALL: [EdgeMetric; 3] = [Self::Reliability, Self::TechDebtSpread, Self::Bandwidth];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Reliability => "reliability",
+            Self::TechDebtSpread => "debt spread",
+            Self::Bandwidth => "bandwidth",
+        }
+    }
+
+    pub fn get(&self, edge: &SystemEdge) -> f64 {
+        match self {
+            Self::Reliability => edge.reliability,
+            Self::TechDebtSpread => edge.tech_debt_spread,
+            Self::Bandwidth => edge.bandwidth,
+        }
+    }
+
+    pub fn set(&self, edge: &mut SystemEdge, value: f64) {
+        match self {
+            Self::Reliability => edge.reliability = value,
+            Self::TechDebtSpread => edge.tech_debt_spread = value,
+            Self::Bandwidth => edge.bandwidth = value,
+        }
+    }
+
+    fn strip(edge: &SystemEdge) -> SystemEdge {
+        let mut stripped = edge.clone();
+        for metric in Self::ALL {
+            metric.set(&mut stripped, 0.0);
+        }
+        stripped
+    }
+}
+
+// One difference between two versions of a system. Nodes are matched by
+// name and edges by endpoints and name. Tracked metrics get a change each;
+// anything else about a node or edge (type, attributes, distributions...)
+// is recorded as the whole element before and after.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GraphDelta {
+    NodeAdded(SystemNode),
+    NodeRemoved(SystemNode),
+    NodeChanged { node: String, metric: NodeMetric, old: f64, new: f64 },
+    NodeUpdated { old: SystemNode, new: SystemNode },
+    EdgeAdded { from: String, to: String, edge: SystemEdge },
+    EdgeRemoved { from: String, to: String, edge: SystemEdge },
+    EdgeChanged { from: String, to: String, name: String, metric: EdgeMetric, old: f64, new: f64 },
+    EdgeUpdated { from: String, to: String, old: SystemEdge, new: SystemEdge },
+}
+
+impl GraphDelta {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::NodeAdded(node) => format!("{} added", node.name),
+            Self::NodeRemoved(node) => format!("{} removed", node.name),
+            Self::NodeChanged { node, metric, old, new } =>
+                format!("{} {}: {:.2} -> {:.2}", node, metric.name(), old, new),
+            Self::NodeUpdated { new, .. } => format!("{} reconfigured", new.name),
+            Self::EdgeAdded { from, to, .. } => format!("{} -> {} connected", from, to),
+            Self::EdgeRemoved { from, to, .. } => format!("{} -> {} disconnected", from, to),
+            Self::EdgeChanged { from, to, metric, old, new, .. } =>
+                format!("{} -> {} {}: {:.2} -> {:.2}", from, to, metric.name(), old, new),
+            Self::EdgeUpdated { from, to, .. } => format!("{} -> {} reconfigured", from, to),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphDiff {
+    pub changes: Vec<GraphDelta>,
+}
+
+impl GraphDiff {
+    // Everything that differs going from `before` to `after`, in graph order
+    pub fn between(before: &SystemGraph, after: &SystemGraph) -> Self {
+        let mut changes = Vec::new();
+        for old in before.graph.node_weights() {
+            match after.node(&old.name) {
+                None => changes.push(GraphDelta::NodeRemoved(old.clone())),
+                Some(new) => {
+                    for metric in NodeMetric::ALL {
+                        let (old_value, new_value) = (metric.get(old), metric.get(new));
+                        if old_value != new_value {
+                            changes.push(GraphDelta::NodeChanged {
+                                node: old.name.clone(), metric, old: old_value, new: new_value,
+                            });
+                        }
+                    }
+                    if NodeMetric::strip(old) != NodeMetric::strip(new) {
+                        changes.push(GraphDelta::NodeUpdated { old: old.clone(), new: new.clone() });
+                    }
+                }
+            }
+        }
+        changes.extend(after.graph.node_weights()
+            .filter(|node| before.node(&node.name).is_none())
+            .map(|node| GraphDelta::NodeAdded(node.clone())));
+
+        let mut unmatched = edges(after);
+        for (from, to, old) in edges(before) {
+            let found = unmatched.iter()
+                .position(|(f, t, edge)| *f == from && *t == to && edge.name == old.name);
+            let Some(position) = found else {
+                changes.push(GraphDelta::EdgeRemoved { from, to, edge: old.clone() });
+                continue;
+            };
+            let (_, _, new) = unmatched.remove(position);
+            for metric in EdgeMetric::ALL {
+                let (old_value, new_value) = (metric.get(old), metric.get(new));
+                if old_value != new_value {
+                    changes.push(GraphDelta::EdgeChanged {
+                        from: from.clone(), to: to.clone(), name: old.name.clone(),
+                        metric, old: old_value, new: new_value,
+                    });
+                }
+            }
+            if EdgeMetric::strip(old) != EdgeMetric::strip(new) {
+                changes.push(GraphDelta::EdgeUpdated { from, to, old: old.clone(), new: new.clone() });
+            }
+        }
+        changes.extend(unmatched.into_iter()
+            .map(|(from, to, edge)| GraphDelta::EdgeAdded { from, to, edge: edge.clone() }));
+
+        Self { changes }
+    }
+
+    // Replay the diff onto the system it was taken from, turning it into the
+    // system it was taken against. Fails, changing nothing, if the system doesn't match.
+    pub fn apply(&self, system: &mut SystemGraph) -> Result<(), GraphError> {
+        let mut scratch = system.clone();
+        self.apply_to(&mut scratch)?;
+        *system = scratch;
+        Ok(())
+    }
+
+    fn apply_to(&self, system: &mut SystemGraph) -> Result<(), GraphError> {
+        // New nodes first and removed ones last, so every edge has both ends
+        for change in &self.changes {
+            if let GraphDelta::NodeAdded(node) = change {
+                if system.node_indices.contains_key(&node.name) {
+                    return Err(GraphError::DuplicateNode(node.name.clone()));
+                }
+                system.add_node(node.clone());
+            }
+        }
+        for change in &self.changes {
+            match change {
+                GraphDelta::NodeChanged { node, metric, new, .. } => {
+                    let node = system.node_mut(node).ok_or_else(|| GraphError::UnknownNode(node.clone()))?;
+                    metric.set(node, *new);
+                }
+                GraphDelta::NodeUpdated { new, .. } => {
+                    let node = system.node_mut(&new.name).ok_or_else(|| GraphError::UnknownNode(new.name.clone()))?;
+                    *node = new.clone();
+                }
+                GraphDelta::EdgeRemoved { from, to, edge } => {
+                    let idx = named_edge(system, from, to, &edge.name)?;
+                    system.graph.remove_edge(idx);
+                }
+                GraphDelta::EdgeChanged { from, to, name, metric, new, .. } => {
+                    let idx = named_edge(system, from, to, name)?;
+                    metric.set(&mut system.graph[idx], *new);
+                }
+                GraphDelta::EdgeUpdated { from, to, old, new } => {
+                    let idx = named_edge(system, from, to, &old.name)?;
+                    system.graph[idx] = new.clone();
+                }
+                GraphDelta::EdgeAdded { from, to, edge } => {
+                    system.add_edge(from, to, edge.clone())
+                        .ok_or_else(|| GraphError::UnknownEdge { from: from.clone(), to: to.clone() })?;
+                }
+                GraphDelta::NodeAdded(_) | GraphDelta::NodeRemoved(_) => {}
+            }
+        }
+        for change in &self.changes {
+            if let GraphDelta::NodeRemoved(node) = change {
+                system.remove_node(&node.name)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    // Net change to one field of a node that exists on both sides, or 0.0
+    pub fn node_delta(&self, node: &str, metric: NodeMetric) -> f64 {
+        self.changes.iter()
+            .map(|change| match change {
+                GraphDelta::NodeChanged { node: name, metric: changed, old, new }
+                    if name == node && *changed == metric => new - old,
+                _ => 0.0,
+            })
+            .sum()
+    }
+
+    // Nodes with at least one changed field, in the order they first changed
+    pub fn changed_nodes(&self) -> Vec<&str> {
+        let mut nodes: Vec<&str> = Vec::new();
+        for change in &self.changes {
+            if let GraphDelta::NodeChanged { node, .. } = change {
+                if !nodes.contains(&node.as_str()) {
+                    nodes.push(node);
+                }
+            }
+        }
+        nodes
+    }
+}
+
+fn named_edge(system: &SystemGraph, from: &str, to: &str, name: &str) -> Result<EdgeIndex, GraphError> {
+    let missing = || GraphError::UnknownEdge { from: from.to_string(), to: to.to_string() };
+    let from_idx = *system.node_indices.get(from).ok_or_else(missing)?;
+    let to_idx = *system.node_indices.get(to).ok_or_else(missing)?;
+    system.graph.edges_connecting(from_idx, to_idx)
+        .find(|edge| edge.weight().name == name)
+        .map(|edge| edge.id())
+        .ok_or_else(missing)
+}
+
+fn edges(system: &SystemGraph) -> Vec<(String, String, &SystemEdge)> {
+    system.graph.edge_indices()
+        .map(|idx| {
+            let (from, to) = system.graph.edge_endpoints(idx).expect("edge index from this graph");
+            (system.graph[from].name.clone(), system.graph[to].name.clone(), &system.graph[idx])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{GraphChange, ArchitectureType, DistributionType, create_architecture};
+    use crate::test_utils::create_test_graph;
+
+    #[test]
+    fn test_identical_graphs_have_no_diff() {
+        let system = create_test_graph();
+        assert!(GraphDiff::between(&system, &system.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_field_changes_and_structure() {
+        let before = create_test_graph();
+        let mut after = before.clone();
+        after.node_mut("test_node_1").unwrap().tech_debt = 25.0;
+        after.node_mut("test_node_2").unwrap().health = 80.0;
+        after.apply_change(&GraphChange::SplitNode {
+            node: "test_node_2".into(),
+            new_name: "test_node_3".into(),
+            moved_neighbors: vec![],
+        }).unwrap();
+        after.remove_edge("test_node_1", "test_node_2").unwrap();
+
+        let diff = GraphDiff::between(&before, &after);
+        assert_eq!(diff.node_delta("test_node_1", NodeMetric::TechDebt), 15.0);
+        assert_eq!(diff.node_delta("test_node_2", NodeMetric::Health), -20.0);
+        assert_eq!(diff.node_delta("test_node_1", NodeMetric::Health), 0.0);
+        assert!(diff.changed_nodes().starts_with(&["test_node_1", "test_node_2"]));
+        assert!(diff.changes.iter().any(|change| matches!(change,
+            GraphDelta::NodeAdded(node) if node.name == "test_node_3")));
+        assert!(diff.changes.iter().any(|change| matches!(change,
+            GraphDelta::EdgeRemoved { from, to, .. } if from == "test_node_1" && to == "test_node_2")));
+        assert!(!diff.changes.iter().any(|change| matches!(change, GraphDelta::NodeRemoved(_))));
+    }
+
+    #[test]
+    fn test_applying_a_diff_reproduces_the_target() {
+        let before = create_architecture(ArchitectureType::Monolith);
+        let mut after = before.clone();
+        after.node_mut("core_service").unwrap().tech_debt += 12.5;
+        after.node_mut("core_service").unwrap().attributes.push("legacy".into());
+        after.node_mut("database").unwrap().latency = DistributionType::Exponential { rate: 0.1 };
+        after.remove_node("cache").unwrap();
+        after.add_node(SystemNode { name: "queue".into(), ..Default::default() });
+        after.add_edge("core_service", "queue", SystemEdge { name: "publish".into(), ..Default::default() });
+        let edge = after.graph.edge_indices().next().unwrap();
+        after.graph[edge].reliability = 0.5;
+
+        let diff = GraphDiff::between(&before, &after);
+        assert!(diff.changes.iter().any(|change| matches!(change, GraphDelta::NodeUpdated { .. })));
+        let mut replayed = before.clone();
+        diff.apply(&mut replayed).unwrap();
+        assert!(GraphDiff::between(&replayed, &after).is_empty());
+
+        // It only fits the system it was taken from
+        let target = after.clone();
+        assert!(diff.apply(&mut after).is_err());
+        assert!(GraphDiff::between(&after, &target).is_empty());
+    }
+}
+
+// Easter egg: "git diff for architectures, minus the merge conflicts 🔀"
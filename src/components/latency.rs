@@ -0,0 +1,171 @@
+use petgraph::stable_graph::NodeIndex;
+use petgraph::Direction;
+use rand::Rng;
+use super::critical_path::traffic_entry_points;
+use super::system_graph::{SystemGraph, SystemNode};
+
+// Keeps path enumeration bounded on densely connected architectures
+pub const MAX_REQUEST_PATHS: usize = 64;
+
+// Percentiles of end-to-end latency for one request path, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LatencyStats {
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub requests: usize,
+}
+
+impl LatencyStats {
+    pub fn from_samples(samples: &[f64]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        Self {
+            p50: percentile(&sorted, 0.50),
+            p95: percentile(&sorted, 0.95),
+            p99: percentile(&sorted, 0.99),
+            requests: sorted.len(),
+        }
+    }
+}
+
+// Nearest-rank percentile of already sorted samples (0.0 when there are none)
+pub fn percentile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (q.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+// Every route a request can take from an entry point down to a leaf. A path
+// also ends where all further calls would loop back onto itself.
+pub fn request_paths(system: &SystemGraph) -> Vec<Vec<String>> {
+    let mut paths = Vec::new();
+    for entry in traffic_entry_points(system) {
+        let mut path = vec![entry];
+        walk_paths(system, &mut path, &mut paths);
+    }
+    paths
+}
+
+fn walk_paths(system: &SystemGraph, path: &mut Vec<NodeIndex>, paths: &mut Vec<Vec<String>>) {
+    if paths.len() >= MAX_REQUEST_PATHS {
+        return;
+    }
+    let last = *path.last().expect("paths start at an entry point");
+    let next: Vec<_> = system.graph.neighbors_directed(last, Direction::Outgoing)
+        .filter(|idx| !path.contains(idx))
+        .collect();
+
+    if next.is_empty() {
+        paths.push(path.iter().map(|&idx| system.graph[idx].name.clone()).collect());
+        return;
+    }
+    // neighbors() yields the newest edge first; walk in insertion order instead
+    for idx in next.into_iter().rev() {
+        path.push(idx);
+        walk_paths(system, path, paths);
+        path.pop();
+    }
+}
+
+// Time a node takes to answer. Tech debt slows everything down: a node at
+// 100% debt is twice as slow as its distribution says.
+pub fn node_latency<R: Rng + ?Sized>(node: &SystemNode, rng: &mut R) -> f64 {
+    node.latency.sample(rng).max(0.0) * (1.0 + node.tech_debt.clamp(0.0, 100.0) / 100.0)
+}
+
+// Sample one request along `path`: every node's latency plus every hop between them
+pub fn path_latency<R: Rng + ?Sized>(system: &SystemGraph, path: &[String], rng: &mut R) -> f64 {
+    let nodes: f64 = path.iter()
+        .filter_map(|name| system.node(name))
+        .map(|node| node_latency(node, rng))
+        .sum();
+    let hops: f64 = path.windows(2)
+        .filter_map(|pair| {
+            let from = *system.node_indices.get(&pair[0])?;
+            let to = *system.node_indices.get(&pair[1])?;
+            let edge = system.graph.find_edge(from, to)?;
+            Some(system.graph[edge].latency.sample(rng).max(0.0))
+        })
+        .sum();
+    nodes + hops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{DistributionType, SystemEdge};
+    use crate::resources::SimRng;
+
+    fn fixed(ms: f64) -> DistributionType {
+        DistributionType::Normal { mean: ms, std_dev: 0.0 }
+    }
+
+    fn node(name: &str, ms: f64) -> SystemNode {
+        SystemNode { name: name.into(), latency: fixed(ms), ..Default::default() }
+    }
+
+    fn edge(ms: f64) -> SystemEdge {
+        SystemEdge { latency: fixed(ms), ..Default::default() }
+    }
+
+    // gateway -> core -> {db, cache}
+    fn create_system() -> SystemGraph {
+        let mut system = SystemGraph::new();
+        system.add_node(node("gateway", 10.0));
+        system.add_node(node("core", 100.0));
+        system.add_node(node("db", 50.0));
+        system.add_node(node("cache", 5.0));
+        system.add_edge("gateway", "core", edge(1.0));
+        system.add_edge("core", "db", edge(2.0));
+        system.add_edge("core", "cache", edge(3.0));
+        system
+    }
+
+    #[test]
+    fn test_request_paths_from_entry_to_leaves() {
+        let system = create_system();
+        assert_eq!(request_paths(&system), vec![
+            vec!["gateway", "core", "db"],
+            vec!["gateway", "core", "cache"],
+        ]);
+    }
+
+    #[test]
+    fn test_cycles_do_not_loop_forever() {
+        let mut system = create_system();
+        system.add_edge("db", "gateway", edge(0.0));
+        // No node is uncalled any more, so without a gateway there's no way in
+        assert!(request_paths(&system).is_empty());
+
+        system.node_mut("gateway").unwrap().node_type = "gateway".into();
+        assert_eq!(request_paths(&system), vec![
+            vec!["gateway", "core", "db"],
+            vec!["gateway", "core", "cache"],
+        ]);
+    }
+
+    #[test]
+    fn test_path_latency_sums_nodes_and_hops() {
+        let mut system = create_system();
+        let mut rng = SimRng::new(1);
+        let path: Vec<String> = vec!["gateway".into(), "core".into(), "db".into()];
+        assert_eq!(path_latency(&system, &path, &mut rng), 10.0 + 1.0 + 100.0 + 2.0 + 50.0);
+
+        system.node_mut("core").unwrap().tech_debt = 50.0;
+        assert_eq!(path_latency(&system, &path, &mut rng), 10.0 + 1.0 + 150.0 + 2.0 + 50.0);
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let samples: Vec<f64> = (1..=100).rev().map(f64::from).collect();
+        let stats = LatencyStats::from_samples(&samples);
+        assert_eq!(stats, LatencyStats { p50: 50.0, p95: 95.0, p99: 99.0, requests: 100 });
+        assert_eq!(LatencyStats::from_samples(&[]), LatencyStats::default());
+        assert_eq!(percentile(&[7.0], 0.99), 7.0);
+    }
+}
+
+// Easter egg: "p99 is just p50 on the day of the demo 🐢"
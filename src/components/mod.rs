@@ -1,14 +1,97 @@
 mod system_graph;
+mod graph_diff;
+mod distribution;
+mod transforms;
 mod architecture;
+mod templates;
+mod generator;
+mod failures;
+mod latency;
+mod defects;
+mod analysis;
+mod critical_path;
 
 pub use system_graph::{
     SystemGraph,
     SystemNode,
     SystemEdge,
+    GraphChange,
+    GraphError,
+};
+
+pub use graph_diff::{
+    GraphDiff,
+    GraphDelta,
+    NodeMetric,
+    EdgeMetric,
+};
+
+pub use distribution::{
     DistributionType,
+    DistributionError,
 };
 
+pub use transforms::DistributionTransform;
+
 pub use architecture::{
     ArchitectureType,
+    Architecture,
     create_architecture,
-}; 
\ No newline at end of file
+};
+
+pub use templates::{
+    ArchitectureTemplate,
+    TemplateEdge,
+    TemplateError,
+    TemplateLibrary,
+    TEMPLATE_DIR,
+};
+
+pub use generator::{
+    GeneratorParams,
+    GENERATED_ARCHITECTURE,
+    generate_architecture,
+};
+
+pub use failures::{
+    Outages,
+    CascadeReport,
+    BASE_RECOVERY_SECS,
+    failure_probability,
+    recovery_secs,
+};
+
+pub use latency::{
+    LatencyStats,
+    MAX_REQUEST_PATHS,
+    request_paths,
+    path_latency,
+    percentile,
+};
+
+pub use defects::{
+    Defect,
+    DefectStatus,
+    Severity,
+};
+
+pub use analysis::{
+    GraphAnalysis,
+    NodeAnalysis,
+    articulation_points,
+    dominators,
+    blast_radius,
+    betweenness_centrality,
+    cycles,
+};
+
+pub use critical_path::{
+    REVENUE_ATTRIBUTE,
+    CRITICAL_OVERRIDE_ATTRIBUTE,
+    ENTRY_POINT_ATTRIBUTE,
+    derive_critical_path,
+    traffic_entry_points,
+    is_declared_entry_point,
+    is_pinned_critical,
+    is_revenue_sink,
+};
@@ -0,0 +1,449 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use bevy::prelude::*;
+use serde::{Serialize, Deserialize};
+use super::system_graph::{SystemGraph, SystemNode, SystemEdge};
+use super::distribution::DistributionType;
+
+// Where designers drop new archetypes; every .json or .ron file in here is a template
+pub const TEMPLATE_DIR: &str = "assets/architectures";
+
+// The archetypes that ship with the game, embedded so they work without the assets folder
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    ("monolith.json", include_str!("../../assets/architectures/monolith.json")),
+    ("microservices.json", include_str!("../../assets/architectures/microservices.json")),
+    ("event_driven.json", include_str!("../../assets/architectures/event_driven.json")),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateError {
+    Io { file: String, message: String },
+    Parse { file: String, message: String },
+    UnsupportedFormat(String),
+    NoNodes,
+    DuplicateNode(String),
+    UnknownNode { edge: String, node: String },
+    SelfLoop(String),
+    DuplicateEdge { from: String, to: String },
+    InvalidNode { node: String, field: &'static str, reason: String },
+    InvalidEdge { edge: String, field: &'static str, reason: String },
+    Invalid { file: String, problem: Box<TemplateError> },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { file, message } => write!(f, "could not read '{}': {}", file, message),
+            Self::Parse { file, message } => write!(f, "could not parse '{}': {}", file, message),
+            Self::UnsupportedFormat(file) => write!(f, "'{}' is not a .json or .ron template", file),
+            Self::NoNodes => write!(f, "template has no nodes"),
+            Self::DuplicateNode(name) => write!(f, "node '{}' is defined more than once", name),
+            Self::UnknownNode { edge, node } => write!(f, "edge '{}' refers to unknown node '{}'", edge, node),
+            Self::SelfLoop(edge) => write!(f, "edge '{}' connects a node to itself", edge),
+            Self::DuplicateEdge { from, to } => write!(f, "more than one edge from '{}' to '{}'", from, to),
+            Self::InvalidNode { node, field, reason } => write!(f, "node '{}': {} {}", node, field, reason),
+            Self::InvalidEdge { edge, field, reason } => write!(f, "edge '{}': {} {}", edge, field, reason),
+            Self::Invalid { file, problem } => write!(f, "'{}': {}", file, problem),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+// An edge as written in a template: endpoints by node name plus the edge itself
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "FlatEdge", into = "FlatEdge")]
+pub struct TemplateEdge {
+    pub from: String,
+    pub to: String,
+    pub edge: SystemEdge,
+}
+
+// The on-disk shape: endpoints and edge fields side by side. Spelled out
+// rather than `#[serde(flatten)]`, which RON can't read.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct FlatEdge {
+    from: String,
+    to: String,
+    name: String,
+    reliability: f64,
+    latency: DistributionType,
+    tech_debt_spread: f64,
+    bandwidth: f64,
+    failure_rate: DistributionType,
+}
+
+impl Default for FlatEdge {
+    fn default() -> Self {
+        TemplateEdge { from: String::new(), to: String::new(), edge: SystemEdge::default() }.into()
+    }
+}
+
+impl From<TemplateEdge> for FlatEdge {
+    fn from(TemplateEdge { from, to, edge }: TemplateEdge) -> Self {
+        Self {
+            from,
+            to,
+            name: edge.name,
+            reliability: edge.reliability,
+            latency: edge.latency,
+            tech_debt_spread: edge.tech_debt_spread,
+            bandwidth: edge.bandwidth,
+            failure_rate: edge.failure_rate,
+        }
+    }
+}
+
+impl From<FlatEdge> for TemplateEdge {
+    fn from(flat: FlatEdge) -> Self {
+        Self {
+            from: flat.from,
+            to: flat.to,
+            edge: SystemEdge {
+                name: flat.name,
+                reliability: flat.reliability,
+                latency: flat.latency,
+                tech_debt_spread: flat.tech_debt_spread,
+                bandwidth: flat.bandwidth,
+                failure_rate: flat.failure_rate,
+            },
+        }
+    }
+}
+
+// A starting architecture described as data. Omitted node and edge fields
+// take their defaults (full health, no debt, perfect reliability...).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchitectureTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub nodes: Vec<SystemNode>,
+    #[serde(default)]
+    pub edges: Vec<TemplateEdge>,
+}
+
+impl ArchitectureTemplate {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|err| err.to_string())
+    }
+
+    pub fn from_ron(ron: &str) -> Result<Self, String> {
+        ron::from_str(ron).map_err(|err| err.to_string())
+    }
+
+    // Parse a template, picking the format from the file extension
+    pub fn parse(file: &str, contents: &str) -> Result<Self, TemplateError> {
+        let parsed = match Path::new(file).extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(contents),
+            Some("ron") => Self::from_ron(contents),
+            _ => return Err(TemplateError::UnsupportedFormat(file.to_string())),
+        };
+        parsed.map_err(|message| TemplateError::Parse { file: file.to_string(), message })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TemplateError> {
+        let path = path.as_ref();
+        let file = path.display().to_string();
+        let contents = fs::read_to_string(path)
+            .map_err(|err| TemplateError::Io { file: file.clone(), message: err.to_string() })?;
+        Self::parse(&file, &contents)
+    }
+
+    // Every problem in the template, not just the first one
+    pub fn validate(&self) -> Vec<TemplateError> {
+        let mut errors = Vec::new();
+        if self.nodes.is_empty() {
+            errors.push(TemplateError::NoNodes);
+        }
+
+        let mut names = HashSet::new();
+        for node in &self.nodes {
+            if !names.insert(node.name.as_str()) {
+                errors.push(TemplateError::DuplicateNode(node.name.clone()));
+            }
+            validate_node(node, &mut errors);
+        }
+
+        let mut connections = HashSet::new();
+        for TemplateEdge { from, to, edge } in &self.edges {
+            let label = edge_label(from, to, edge);
+            for endpoint in [from, to] {
+                if !names.contains(endpoint.as_str()) {
+                    errors.push(TemplateError::UnknownNode { edge: label.clone(), node: endpoint.clone() });
+                }
+            }
+            if from == to {
+                errors.push(TemplateError::SelfLoop(label.clone()));
+            } else if !connections.insert((from, to)) {
+                errors.push(TemplateError::DuplicateEdge { from: from.clone(), to: to.clone() });
+            }
+            validate_edge(&label, edge, &mut errors);
+        }
+        errors
+    }
+
+    pub fn build(&self) -> Result<SystemGraph, Vec<TemplateError>> {
+        let errors = self.validate();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut system = SystemGraph::new();
+        system.in_bulk(|system| {
+            for node in &self.nodes {
+                system.add_node(node.clone());
+            }
+            for TemplateEdge { from, to, edge } in &self.edges {
+                system.add_edge(from, to, edge.clone()).expect("edge endpoints validated");
+            }
+        });
+        Ok(system)
+    }
+}
+
+fn check_range(value: f64, min: f64, max: f64) -> Option<String> {
+    if !value.is_finite() || value < min || value > max {
+        Some(format!("must be between {} and {}, got {}", min, max, value))
+    } else {
+        None
+    }
+}
+
+fn check_distribution(distribution: &DistributionType) -> Option<String> {
+    distribution.validate()
+        .err()
+        .map(|err| format!("{} distribution: {}", distribution.name(), err))
+}
+
+fn validate_node(node: &SystemNode, errors: &mut Vec<TemplateError>) {
+    let checks = [
+        ("name", node.name.is_empty().then(|| "must not be empty".to_string())),
+        ("health", check_range(node.health, 0.0, 100.0)),
+        ("tech_debt", check_range(node.tech_debt, 0.0, 100.0)),
+        ("contagion_risk", check_range(node.contagion_risk, 0.0, 1.0)),
+        ("operating_cost", check_range(node.operating_cost, 0.0, f64::MAX)),
+        ("defect_rate", check_range(node.defect_rate, 0.0, f64::MAX)),
+        ("latency", check_distribution(&node.latency)),
+        ("failure_rate", check_distribution(&node.failure_rate)),
+    ];
+    errors.extend(checks.into_iter().filter_map(|(field, reason)| {
+        reason.map(|reason| TemplateError::InvalidNode { node: node.name.clone(), field, reason })
+    }));
+}
+
+// Edge names are optional, so errors fall back on naming the endpoints
+fn edge_label(from: &str, to: &str, edge: &SystemEdge) -> String {
+    if edge.name.is_empty() {
+        format!("{} -> {}", from, to)
+    } else {
+        edge.name.clone()
+    }
+}
+
+fn validate_edge(label: &str, edge: &SystemEdge, errors: &mut Vec<TemplateError>) {
+    let checks = [
+        ("reliability", check_range(edge.reliability, f64::MIN_POSITIVE, 1.0)),
+        ("tech_debt_spread", check_range(edge.tech_debt_spread, 0.0, 1.0)),
+        ("bandwidth", check_range(edge.bandwidth, 0.0, f64::MAX)),
+        ("latency", check_distribution(&edge.latency)),
+        ("failure_rate", check_distribution(&edge.failure_rate)),
+    ];
+    errors.extend(checks.into_iter().filter_map(|(field, reason)| {
+        reason.map(|reason| TemplateError::InvalidEdge { edge: label.to_string(), field, reason })
+    }));
+}
+
+// Every architecture the game knows about, in the order they were loaded
+#[derive(Resource, Debug, Clone)]
+pub struct TemplateLibrary {
+    pub templates: Vec<ArchitectureTemplate>,
+}
+
+impl Default for TemplateLibrary {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl TemplateLibrary {
+    pub fn builtin() -> Self {
+        let templates = BUILTIN_TEMPLATES.iter()
+            .map(|(file, contents)| {
+                ArchitectureTemplate::parse(file, contents)
+                    .unwrap_or_else(|err| panic!("built-in template is broken: {}", err))
+            })
+            .collect();
+        Self { templates }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ArchitectureTemplate> {
+        self.templates.iter().find(|template| template.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.templates.iter().map(|template| template.name.as_str())
+    }
+
+    // Add a template, replacing any existing one with the same name
+    pub fn insert(&mut self, template: ArchitectureTemplate) {
+        match self.templates.iter_mut().find(|t| t.name.eq_ignore_ascii_case(&template.name)) {
+            Some(existing) => *existing = template,
+            None => self.templates.push(template),
+        }
+    }
+
+    // Load every template in `dir` on top of the built-ins. Broken files are
+    // skipped and reported, so one bad template doesn't take the game down.
+    pub fn load_dir(&mut self, dir: impl AsRef<Path>) -> Vec<TemplateError> {
+        let dir = dir.as_ref();
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) => {
+                return vec![TemplateError::Io { file: dir.display().to_string(), message: err.to_string() }];
+            }
+        };
+
+        let mut paths: Vec<_> = entries.filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| matches!(path.extension().and_then(|ext| ext.to_str()), Some("json" | "ron")))
+            .collect();
+        paths.sort();
+
+        let mut errors = Vec::new();
+        for path in paths {
+            match ArchitectureTemplate::load(&path) {
+                Ok(template) => {
+                    let problems = template.validate();
+                    if problems.is_empty() {
+                        self.insert(template);
+                    } else {
+                        let file = path.display().to_string();
+                        errors.extend(problems.into_iter().map(|problem| {
+                            TemplateError::Invalid { file: file.clone(), problem: Box::new(problem) }
+                        }));
+                    }
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_templates_are_valid() {
+        let library = TemplateLibrary::builtin();
+        assert_eq!(library.names().collect::<Vec<_>>(), vec!["Monolith", "Microservices", "Event-Driven"]);
+        for template in &library.templates {
+            assert_eq!(template.validate(), vec![], "{} is invalid", template.name);
+        }
+    }
+
+    #[test]
+    fn test_asset_dir_loads_json_and_ron() {
+        let mut library = TemplateLibrary::builtin();
+        let errors = library.load_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/architectures"));
+        assert_eq!(errors, vec![]);
+        assert_eq!(library.templates.len(), 5);
+
+        let mesh = library.get("service mesh").expect("RON template loaded").build().unwrap();
+        assert_eq!(mesh.graph.node_count(), 4);
+        assert_eq!(mesh.graph.edge_count(), 4);
+        let serverless = library.get("Serverless").expect("JSON template loaded").build().unwrap();
+        assert!(serverless.node("order_function").unwrap().critical_path);
+
+        // A template that parses but doesn't validate is skipped, and its
+        // problems say which file they came from
+        let dir = std::env::temp_dir().join(format!("templates_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let broken = dir.join("broken.json");
+        fs::write(&broken, r#"{ "name": "Broken", "nodes": [{ "name": "a" }, { "name": "a" }] }"#).unwrap();
+        let errors = library.load_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let file = broken.display().to_string();
+        assert_eq!(errors, vec![TemplateError::Invalid {
+            file: file.clone(),
+            problem: Box::new(TemplateError::DuplicateNode("a".into())),
+        }]);
+        assert_eq!(errors[0].to_string(), format!("'{}': node 'a' is defined more than once", file));
+        assert!(library.get("Broken").is_none());
+    }
+
+    #[test]
+    fn test_omitted_fields_take_defaults() {
+        let json = r#"{
+            "name": "Tiny",
+            "nodes": [{ "name": "a", "tech_debt": 5.0 }, { "name": "b" }],
+            "edges": [{ "from": "a", "to": "b", "name": "a_to_b" }]
+        }"#;
+        let system = ArchitectureTemplate::from_json(json).unwrap().build().unwrap();
+        let a = system.node("a").unwrap();
+        assert_eq!((a.health, a.tech_debt), (100.0, 5.0));
+        assert_eq!(system.graph.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_validation_names_the_offender() {
+        let ron = r#"(
+            name: "Broken",
+            nodes: [
+                (name: "api", tech_debt: 150.0),
+                (name: "db"),
+                (name: "db", latency: Normal(mean: 10.0, std_dev: -1.0)),
+            ],
+            edges: [
+                (from: "api", to: "queue", name: "api_to_queue"),
+                (from: "api", to: "db", name: "api_to_db", reliability: 0.0),
+                (from: "db", to: "db", name: "db_loop"),
+            ],
+        )"#;
+        let template = ArchitectureTemplate::from_ron(ron).unwrap();
+        let errors = template.build().unwrap_err();
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+
+        assert!(errors.contains(&TemplateError::DuplicateNode("db".into())));
+        assert!(errors.contains(&TemplateError::UnknownNode { edge: "api_to_queue".into(), node: "queue".into() }));
+        assert!(errors.contains(&TemplateError::SelfLoop("db_loop".into())));
+        assert!(messages.iter().any(|m| m.starts_with("node 'api': tech_debt must be between 0 and 100")));
+        assert!(messages.contains(&"node 'db': latency Normal distribution: std_dev must not be negative, got -1".to_string()));
+        assert!(messages.iter().any(|m| m.starts_with("edge 'api_to_db': reliability")));
+        assert_eq!(errors.len(), 6);
+    }
+
+    #[test]
+    fn test_unnamed_edges_are_named_by_endpoints() {
+        let json = r#"{
+            "name": "Anonymous",
+            "nodes": [{ "name": "api" }],
+            "edges": [{ "from": "api", "to": "queue" }, { "from": "api", "to": "api", "tech_debt_spread": 2.0 }]
+        }"#;
+        let messages: Vec<String> = ArchitectureTemplate::from_json(json).unwrap()
+            .validate().iter().map(ToString::to_string).collect();
+        assert_eq!(messages, vec![
+            "edge 'api -> queue' refers to unknown node 'queue'",
+            "edge 'api -> api' connects a node to itself",
+            "edge 'api -> api': tech_debt_spread must be between 0 and 1, got 2",
+        ]);
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_file() {
+        let err = ArchitectureTemplate::parse("broken.json", "{ not json").unwrap_err();
+        assert!(matches!(err, TemplateError::Parse { ref file, .. } if file == "broken.json"));
+        assert_eq!(
+            ArchitectureTemplate::parse("notes.txt", "").unwrap_err(),
+            TemplateError::UnsupportedFormat("notes.txt".into())
+        );
+    }
+}
+
+// Easter egg: "Infrastructure as code, architecture as JSON, regrets as YAML 📐"
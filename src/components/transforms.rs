@@ -0,0 +1,219 @@
+use serde::{Serialize, Deserialize};
+use super::distribution::{DistributionType, DistributionError};
+
+// A reshaping a card can apply to a distribution, in the distribution's own
+// terms rather than as a nudge to one of its parameters. Transforms compose:
+// each one wraps (or folds into) whatever the distribution already is.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DistributionTransform {
+    ShiftMean(f64),  // Move the whole distribution by this much
+    ScaleSpread(f64),  // Narrow (< 1) or widen (> 1) it around its mean
+    Clamp { min: f64, max: f64 },  // Cap outliers at the bounds, e.g. timeouts
+    Truncate { min: f64, max: f64 },  // Rule out anything beyond the bounds
+    Mix { other: DistributionType, weight: f64 },  // Blend in another behaviour
+}
+
+impl DistributionTransform {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ShiftMean(_) => "Shift",
+            Self::ScaleSpread(_) => "Scale",
+            Self::Clamp { .. } => "Clamp",
+            Self::Truncate { .. } => "Truncate",
+            Self::Mix { .. } => "Mix",
+        }
+    }
+
+    pub fn apply(&self, dist: &DistributionType) -> Result<DistributionType, DistributionError> {
+        match self {
+            Self::ShiftMean(offset) => dist.shift_mean(*offset),
+            Self::ScaleSpread(factor) => dist.scale_spread(*factor),
+            Self::Clamp { min, max } => dist.clamp_to(*min, *max),
+            Self::Truncate { min, max } => dist.truncate(*min, *max),
+            Self::Mix { other, weight } => dist.mix(other.clone(), *weight),
+        }
+    }
+
+    // Undo this transform on a distribution it was applied to, even if other
+    // transforms were layered on since. None if there's nothing to undo.
+    pub fn revert(&self, dist: &DistributionType) -> Option<DistributionType> {
+        peel(dist, self)
+    }
+}
+
+impl DistributionType {
+    // Move the distribution without changing its shape
+    pub fn shift_mean(&self, offset: f64) -> Result<DistributionType, DistributionError> {
+        let shifted = match self {
+            Self::Normal { mean, std_dev } => Self::Normal { mean: mean + offset, std_dev: *std_dev },
+            Self::Shifted { base, offset: existing } => {
+                let total = existing + offset;
+                if total == 0.0 { (**base).clone() } else { Self::Shifted { base: base.clone(), offset: total } }
+            }
+            _ if offset == 0.0 => self.clone(),
+            _ => Self::Shifted { base: Box::new(self.clone()), offset },
+        };
+        shifted.checked()
+    }
+
+    // Stretch or squeeze the distribution around its mean, which stays put
+    pub fn scale_spread(&self, factor: f64) -> Result<DistributionType, DistributionError> {
+        let scaled = match self {
+            Self::Normal { mean, std_dev } if factor > 0.0 => Self::Normal { mean: *mean, std_dev: std_dev * factor },
+            Self::Scaled { base, factor: existing } => {
+                let total = existing * factor;
+                if total == 1.0 { (**base).clone() } else { Self::Scaled { base: base.clone(), factor: total } }
+            }
+            _ if factor == 1.0 => self.clone(),
+            _ => Self::Scaled { base: Box::new(self.clone()), factor },
+        };
+        scaled.checked()
+    }
+
+    pub fn clamp_to(&self, min: f64, max: f64) -> Result<DistributionType, DistributionError> {
+        Self::Clamped { base: Box::new(self.clone()), min, max }.checked()
+    }
+
+    pub fn truncate(&self, min: f64, max: f64) -> Result<DistributionType, DistributionError> {
+        Self::Truncated { base: Box::new(self.clone()), min, max }.checked()
+    }
+
+    // Draw from `other` with probability `weight`, otherwise from this distribution
+    pub fn mix(&self, other: DistributionType, weight: f64) -> Result<DistributionType, DistributionError> {
+        if !(0.0..=1.0).contains(&weight) {
+            return Err(DistributionError::NotAShare { parameter: "weight", value: weight });
+        }
+        Self::Mixture { parts: vec![(1.0 - weight, self.clone()), (weight, other)] }.checked()
+    }
+}
+
+// Remove the outermost layer the transform produced, reapplying the layers above it
+// so they fold into the base the same way they would have without it. Shifts and
+// scales may have merged into a layer or a normal, so they're undone in place.
+fn peel(dist: &DistributionType, transform: &DistributionTransform) -> Option<DistributionType> {
+    use DistributionType as D;
+    use DistributionTransform as T;
+    match (dist, transform) {
+        (D::Shifted { .. } | D::Normal { .. }, T::ShiftMean(offset)) => dist.shift_mean(-offset).ok(),
+        (D::Scaled { .. } | D::Normal { .. }, T::ScaleSpread(factor)) => dist.scale_spread(1.0 / factor).ok(),
+        (D::Clamped { base, min, max }, T::Clamp { min: lo, max: hi })
+        | (D::Truncated { base, min, max }, T::Truncate { min: lo, max: hi })
+            if min == lo && max == hi => Some((**base).clone()),
+        (D::Mixture { parts }, T::Mix { other, weight })
+            if parts.len() == 2 && parts[1] == (*weight, other.clone()) => Some(parts[0].1.clone()),
+        (D::Shifted { base, offset }, _) => peel(base, transform)?.shift_mean(*offset).ok(),
+        (D::Scaled { base, factor }, _) => peel(base, transform)?.scale_spread(*factor).ok(),
+        (D::Clamped { base, min, max }, _) => peel(base, transform)?.clamp_to(*min, *max).ok(),
+        (D::Truncated { base, min, max }, _) => peel(base, transform)?.truncate(*min, *max).ok(),
+        (D::Mixture { parts }, _) if parts.len() == 2 => {
+            Some(D::Mixture { parts: vec![(parts[0].0, peel(&parts[0].1, transform)?), parts[1].clone()] })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64, tolerance: f64) -> bool {
+        (a - b).abs() <= tolerance * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn test_shift_and_scale_fold_into_normals() {
+        let latency = DistributionType::Normal { mean: 100.0, std_dev: 20.0 };
+        let faster = DistributionTransform::ShiftMean(-30.0).apply(&latency).unwrap();
+        assert_eq!(faster, DistributionType::Normal { mean: 70.0, std_dev: 20.0 });
+        let steadier = DistributionTransform::ScaleSpread(0.5).apply(&faster).unwrap();
+        assert_eq!(steadier, DistributionType::Normal { mean: 70.0, std_dev: 10.0 });
+
+        // Other shapes are wrapped, and repeated transforms merge into one layer
+        let tail = DistributionType::Pareto { scale: 10.0, shape: 3.0 };
+        let shifted = tail.shift_mean(5.0).unwrap().shift_mean(5.0).unwrap();
+        assert_eq!(shifted, DistributionType::Shifted { base: Box::new(tail.clone()), offset: 10.0 });
+        assert!(approx(shifted.mean(), tail.mean() + 10.0, 1e-12));
+        assert_eq!(shifted.shift_mean(-10.0).unwrap(), tail);
+
+        let narrowed = tail.scale_spread(0.5).unwrap();
+        assert!(approx(narrowed.mean(), tail.mean(), 1e-12));
+        assert!(approx(narrowed.variance(), tail.variance() / 4.0, 1e-12));
+        assert!(approx(narrowed.quantile(0.9), tail.mean() + 0.5 * (tail.quantile(0.9) - tail.mean()), 1e-12));
+    }
+
+    #[test]
+    fn test_clamp_truncate_and_mix() {
+        let latency = DistributionType::Exponential { rate: 0.01 };
+
+        // Timeouts: nothing takes longer than 200ms, so the tail piles up there
+        let timed_out = latency.clamp_to(0.0, 200.0).unwrap();
+        assert_eq!(timed_out.quantile(0.99), 200.0);
+        assert!(approx(timed_out.mean(), 100.0 * (1.0 - (-2.0f64).exp()), 0.01));
+        assert_eq!(timed_out.cdf(200.0), 1.0);
+
+        // Truncation drops the tail instead, so the mean falls further
+        let truncated = latency.truncate(0.0, 200.0).unwrap();
+        assert!(truncated.mean() < timed_out.mean());
+        assert!(truncated.quantile(0.999) <= 200.0);
+        assert!(approx(truncated.cdf(100.0), (1.0 - (-1.0f64).exp()) / (1.0 - (-2.0f64).exp()), 1e-9));
+
+        // A 10% chance of hitting a slow path
+        let slow = DistributionType::Normal { mean: 1000.0, std_dev: 0.0 };
+        let mixed = latency.mix(slow, 0.1).unwrap();
+        assert!(approx(mixed.mean(), 0.9 * 100.0 + 0.1 * 1000.0, 1e-12));
+        assert!(approx(mixed.cdf(999.0), 0.9 * latency.cdf(999.0), 1e-12));
+        assert!(approx(mixed.quantile(0.95), 1000.0, 1e-6));
+
+        assert_eq!(latency.clamp_to(5.0, 1.0), Err(DistributionError::EmptyRange { min: 5.0, max: 1.0 }));
+        assert_eq!(latency.truncate(-10.0, -1.0), Err(DistributionError::EmptyRange { min: -10.0, max: -1.0 }));
+        assert_eq!(
+            latency.mix(latency.clone(), 1.5),
+            Err(DistributionError::NotAShare { parameter: "weight", value: 1.5 })
+        );
+    }
+
+    #[test]
+    fn test_revert_peels_the_matching_layer() {
+        let base = DistributionType::LogNormal { location: 4.0, scale: 0.5 };
+        let clamp = DistributionTransform::Clamp { min: 0.0, max: 150.0 };
+        let shift = DistributionTransform::ShiftMean(20.0);
+        let mix = DistributionTransform::Mix { other: DistributionType::Normal { mean: 300.0, std_dev: 10.0 }, weight: 0.2 };
+
+        let clamped = clamp.apply(&base).unwrap();
+        let shifted = shift.apply(&clamped).unwrap();
+        let mixed = mix.apply(&shifted).unwrap();
+
+        // The clamp expires first, while the layers applied after it stay on
+        let unclamped = clamp.revert(&mixed).unwrap();
+        assert_eq!(unclamped, mix.apply(&shift.apply(&base).unwrap()).unwrap());
+        assert_eq!(shift.revert(&mix.revert(&unclamped).unwrap()).unwrap(), base);
+        assert!(clamp.revert(&base).is_none());
+
+        // A shift under a clamp comes off underneath it, so the clamp still
+        // applies to the unshifted distribution
+        let clamped_after_shift = clamp.apply(&shift.apply(&base).unwrap()).unwrap();
+        assert_eq!(shift.revert(&clamped_after_shift).unwrap(), clamped);
+        let normal = DistributionType::Normal { mean: 100.0, std_dev: 20.0 };
+        let narrowed = DistributionTransform::ScaleSpread(0.5);
+        let capped = clamp.apply(&narrowed.apply(&shift.apply(&normal).unwrap()).unwrap()).unwrap();
+        let unshifted = shift.revert(&capped).unwrap();
+        assert_eq!(unshifted, clamp.apply(&narrowed.apply(&normal).unwrap()).unwrap());
+        assert_eq!(narrowed.revert(&unshifted).unwrap(), clamp.apply(&normal).unwrap());
+        assert!(shift.revert(&base).is_none());
+    }
+
+    #[test]
+    fn test_transformed_distributions_round_trip_through_serde() {
+        let dist = DistributionType::Normal { mean: 50.0, std_dev: 5.0 }
+            .truncate(0.0, 80.0).unwrap()
+            .mix(DistributionType::Poisson { lambda: 4.0 }, 0.25).unwrap();
+        let json = serde_json::to_string(&dist).unwrap();
+        assert_eq!(serde_json::from_str::<DistributionType>(&json).unwrap(), dist);
+
+        let transform = DistributionTransform::Clamp { min: 0.0, max: 250.0 };
+        let ron = ron::to_string(&transform).unwrap();
+        assert_eq!(ron::from_str::<DistributionTransform>(&ron).unwrap(), transform);
+    }
+}
+
+// Easter egg: "Can't fix the latency? Truncate the tail and call it an SLO 📉"
@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+use crate::cards::ActiveEffects;
+use crate::components::{SystemGraph, percentile};
+use crate::resources::{GameResources, SimulationConfig, DefectTracker, ArchitectureMigration};
+use crate::simulation::Simulation;
+
+pub const DEFAULT_FORECAST_RUNS: u32 = 32;
+pub const DEFAULT_FORECAST_SPRINTS: u32 = 5;
+// Fewer, longer ticks than a real sprint keep forecasts cheap. Rates are scaled
+// to the tick length, so averages hold, but failures land in coarser steps.
+pub const DEFAULT_FORECAST_TICKS: u32 = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForecastParams {
+    pub runs: u32,  // Simulations played out, each with its own seed
+    pub sprints: u32,  // How far ahead to look
+    pub ticks_per_sprint: u32,
+}
+
+impl Default for ForecastParams {
+    fn default() -> Self {
+        Self {
+            runs: DEFAULT_FORECAST_RUNS,
+            sprints: DEFAULT_FORECAST_SPRINTS,
+            ticks_per_sprint: DEFAULT_FORECAST_TICKS,
+        }
+    }
+}
+
+// The live game a forecast starts from. Open defects, an architecture
+// migration's dual-running costs and lingering card effects carry into every
+// run along with the system and resources.
+#[derive(Debug, Clone, Copy)]
+pub struct LiveGame<'a> {
+    pub system: &'a SystemGraph,
+    pub resources: &'a GameResources,
+    pub defects: &'a DefectTracker,
+    pub migration: &'a ArchitectureMigration,
+    pub effects: &'a ActiveEffects,
+}
+
+// Spread of one value across all runs: the median and the bands holding
+// the middle half and the middle 90% of outcomes
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ConfidenceBand {
+    pub p5: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p95: f64,
+}
+
+impl ConfidenceBand {
+    pub fn from_samples(mut samples: Vec<f64>) -> Self {
+        samples.sort_by(f64::total_cmp);
+        Self {
+            p5: percentile(&samples, 0.05),
+            p25: percentile(&samples, 0.25),
+            p50: percentile(&samples, 0.50),
+            p75: percentile(&samples, 0.75),
+            p95: percentile(&samples, 0.95),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SprintForecast {
+    pub sprint: u32,
+    pub money: ConfidenceBand,
+    pub reputation: ConfidenceBand,
+    pub average_tech_debt: ConfidenceBand,
+    pub node_health: BTreeMap<String, ConfidenceBand>,  // Over the runs the node still exists in
+    pub finished: f64,  // Share of runs where the game has been won or lost by now
+}
+
+// Likely outcomes of the next few sprints if nothing else is played. The
+// first entry is the current state, so every band fans out from a point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Forecast {
+    pub runs: u32,
+    pub from_sprint: u32,
+    pub sprints: Vec<SprintForecast>,
+}
+
+// Where one run stood at the end of a sprint
+struct Outcome {
+    money: f64,
+    reputation: f64,
+    average_tech_debt: f64,
+    node_health: Vec<(String, f64)>,
+    finished: bool,
+}
+
+impl Outcome {
+    fn of(system: &SystemGraph, resources: &GameResources, finished: bool) -> Self {
+        Self {
+            money: resources.money,
+            reputation: resources.reputation,
+            average_tech_debt: system.average_tech_debt(),
+            node_health: system.graph.node_weights().map(|node| (node.name.clone(), node.health)).collect(),
+            finished,
+        }
+    }
+}
+
+// Play the next `params.sprints` sprints out `params.runs` times, each run on a
+// copy of the live game with its own seed derived from `config.seed`.
+// The same inputs always give the same forecast.
+pub fn forecast(game: LiveGame, config: &SimulationConfig, params: &ForecastParams) -> Forecast {
+    let LiveGame { system, resources, .. } = game;
+    let runs = params.runs.max(1);
+    let mut outcomes: Vec<Vec<Outcome>> = (0..=params.sprints).map(|_| Vec::new()).collect();
+    for run in 0..runs {
+        let run_config = SimulationConfig {
+            seed: config.seed.wrapping_add(run as u64),
+            ticks_per_sprint: params.ticks_per_sprint,
+            ..config.clone()
+        };
+        let mut sim = Simulation::new(run_config)
+            .with_system(system.clone())
+            .with_resources(resources.clone())
+            .with_defects(game.defects.clone())
+            .with_migration(game.migration.clone())
+            .with_effects(game.effects.clone());
+        outcomes[0].push(Outcome::of(system, resources, false));
+        for sprint in outcomes.iter_mut().skip(1) {
+            sim.run_sprints(1);
+            let finished = sim.is_finished();
+            let resources = sim.resources().clone();
+            let system = sim.system().expect("simulation has a system");
+            sprint.push(Outcome::of(system, &resources, finished));
+        }
+    }
+
+    let sprints = outcomes.iter().enumerate()
+        .map(|(ahead, outcomes)| summarize(resources.sprint + ahead as u32, outcomes))
+        .collect();
+    Forecast { runs, from_sprint: resources.sprint, sprints }
+}
+
+fn summarize(sprint: u32, outcomes: &[Outcome]) -> SprintForecast {
+    let band = |value: fn(&Outcome) -> f64| ConfidenceBand::from_samples(outcomes.iter().map(value).collect());
+    let mut health: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    for (name, value) in outcomes.iter().flat_map(|outcome| &outcome.node_health) {
+        health.entry(name.clone()).or_default().push(*value);
+    }
+    SprintForecast {
+        sprint,
+        money: band(|outcome| outcome.money),
+        reputation: band(|outcome| outcome.reputation),
+        average_tech_debt: band(|outcome| outcome.average_tech_debt),
+        node_health: health.into_iter()
+            .map(|(name, samples)| (name, ConfidenceBand::from_samples(samples)))
+            .collect(),
+        finished: outcomes.iter().filter(|outcome| outcome.finished).count() as f64 / outcomes.len() as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Architecture, ArchitectureType};
+    use crate::resources::GameRules;
+    use crate::test_utils::create_test_graph;
+
+    fn quick() -> ForecastParams {
+        ForecastParams { runs: 8, sprints: 3, ticks_per_sprint: 10 }
+    }
+
+    // A forecast from a game with no open defects, migration or card effects
+    fn fresh(system: &SystemGraph, resources: &GameResources, config: &SimulationConfig) -> Forecast {
+        let (defects, migration, effects) = Default::default();
+        let game = LiveGame { system, resources, defects: &defects, migration: &migration, effects: &effects };
+        forecast(game, config, &quick())
+    }
+
+    #[test]
+    fn test_bands_fan_out_from_the_current_state() {
+        let system = create_test_graph();
+        let resources = GameResources::default();
+        let config = SimulationConfig { rules: GameRules::endless(), ..SimulationConfig::with_seed(5) };
+        let result = fresh(&system, &resources, &config);
+
+        assert_eq!(result.from_sprint, 1);
+        assert_eq!(result.sprints.iter().map(|s| s.sprint).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        let now = &result.sprints[0];
+        assert_eq!(now.money.p5, resources.money);
+        assert_eq!(now.money.p95, resources.money);
+        assert_eq!(now.node_health.len(), 2);
+
+        for (before, after) in result.sprints.iter().zip(&result.sprints[1..]) {
+            assert!(after.money.p50 < before.money.p50, "operating costs should be paid every sprint");
+            assert!(after.average_tech_debt.p50 >= before.average_tech_debt.p50);
+        }
+        for sprint in &result.sprints {
+            for band in [sprint.money, sprint.reputation].iter().chain(sprint.node_health.values()) {
+                assert!(band.p5 <= band.p25 && band.p25 <= band.p50 && band.p50 <= band.p75 && band.p75 <= band.p95);
+            }
+            assert_eq!(sprint.finished, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_forecasts_are_reproducible() {
+        let system = create_test_graph();
+        let resources = GameResources { sprint: 4, ..Default::default() };
+        let config = SimulationConfig::with_seed(9);
+        assert_eq!(
+            fresh(&system, &resources, &config),
+            fresh(&system, &resources, &config)
+        );
+    }
+
+    #[test]
+    fn test_runs_carry_on_the_live_migration() {
+        let system = create_test_graph();
+        let resources = GameResources::default();
+        let config = SimulationConfig { rules: GameRules::endless(), ..SimulationConfig::with_seed(3) };
+        let mut migration = ArchitectureMigration::default();
+        migration.start(&Architecture::Builtin(ArchitectureType::Monolith), ArchitectureType::Microservices, 2).unwrap();
+        let (defects, effects) = Default::default();
+        let game = LiveGame { system: &system, resources: &resources, defects: &defects, migration: &migration, effects: &effects };
+
+        let migrating = forecast(game, &config, &quick());
+        let standing_still = fresh(&system, &resources, &config);
+        // The migration's first step brings target services up alongside the legacy ones
+        assert!(migrating.sprints[1].node_health.contains_key("api_gateway"));
+        assert!(!standing_still.sprints[1].node_health.contains_key("api_gateway"));
+        assert_eq!(migrating.sprints[0], standing_still.sprints[0]);
+    }
+
+    #[test]
+    fn test_runs_that_end_stay_ended() {
+        let broke = GameResources { money: -1.0e6, ..Default::default() };
+        let result = fresh(&create_test_graph(), &broke, &SimulationConfig::default());
+        assert!(result.sprints[1..].iter().all(|sprint| sprint.finished == 1.0));
+        assert_eq!(result.sprints[1].money, result.sprints[3].money);
+    }
+}
+
+// Easter egg: "70% chance of outage, with scattered pages in the late evening ⛈️"
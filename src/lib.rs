@@ -8,12 +8,26 @@ pub enum GameState {
     Planning,
     Running,
     Paused,
+    SprintReview,  // Execution is over; the sprint report is up before the next planning phase
+    GameOver,
+    Victory,
 }
 
+impl GameState {
+    // Nothing left to plan or run once the game has been won or lost
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::GameOver | Self::Victory)
+    }
+}
+
+pub mod cards;
 pub mod components;
 pub mod resources;
 pub mod systems;
 pub mod plugins;
+pub mod simulation;
+pub mod forecast;
+pub mod preview;
 
 #[cfg(test)]
 pub mod test_utils;
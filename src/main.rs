@@ -1,31 +1,21 @@
 use bevy::prelude::*;
-use bevy_egui::EguiPlugin;
 use devops_entropy::{
-    plugins::UiPlugin,
-    resources::{GameResources, create_initial_system},
-    components::SystemGraph,
-    systems::game_loop::tick_system,
+    components::{TemplateLibrary, TEMPLATE_DIR},
+    plugins::{UiPlugin, SimulationPlugin},
+    resources::SimulationConfig,
+    systems::{quick_save, quick_load},
     GameState,
 };
 
-mod components;
-mod resources;
-mod systems;
-mod plugins;
-
-// Phase tracking for execution state
-#[derive(Resource)]
-struct ExecutionPhase {
-    current_step: usize,
-    steps: Vec<String>,
-    elapsed_time: f64,
-}
-
 fn main() {
     // Set up crash handler first
     setup_crash_handler();
     
     info!("🎮 Starting DevOps Entropy Game");
+
+    let config = simulation_config();
+    info!("Simulation seed: {}", config.seed);
+    let templates = architecture_templates();
     
     App::new()
         // Add core Bevy plugins
@@ -46,120 +36,41 @@ fn main() {
         // Add UI plugin (which includes EguiPlugin)
         .add_plugins(UiPlugin)
         
-        // Add game states
-        .add_state::<GameState>()
-        
-        // Add core resources
-        .insert_resource(ExecutionPhase {
-            current_step: 0,
-            steps: vec![
-                "Apply Changes".into(),
-                "Run Simulation".into(),
-                "Process Defects".into(),
-                "Calculate Revenue".into(),
-                "Update Tech Debt".into(),
-            ],
-            elapsed_time: 0.0,
-        })
-        .insert_resource(GameResources::default())
-        
-        // Add startup system to initialize game
-        .add_systems(Startup, setup_game)
+        // Add the game simulation (states, resources and sprint systems)
+        .insert_resource(templates)
+        .add_plugins(SimulationPlugin { config })
         
-        // Systems that run in specific states
-        .add_systems(OnEnter(GameState::Planning), setup_planning_phase)
-        .add_systems(OnEnter(GameState::Running), setup_execution_phase)
-        .add_systems(Update, 
-            (
-                update_planning_phase.run_if(in_state(GameState::Planning)),
-                (update_execution_phase, tick_system).run_if(in_state(GameState::Running)),
-                handle_window_close,
-            )
-        )
+        .add_systems(Update, handle_window_close)
+        // F5 / F9 quick-save and quick-load between sprints
+        .add_systems(Update, (quick_save, quick_load).run_if(in_state(GameState::Planning)))
         .add_event::<bevy::app::AppExit>() // Add exit event handling
         .run();
         
     info!("👋 Game exited normally");
 }
 
-// Initialize game state and spawn initial system
-fn setup_game(
-    mut commands: Commands,
-    mut next_state: ResMut<NextState<GameState>>,
-) {
-    info!("Setting up initial game state");
-    
-    // Create initial system graph
-    let system = create_initial_system();
-    info!("Created initial system with {} nodes and {} edges", 
-        system.graph.node_count(), 
-        system.graph.edge_count()
-    );
-    
-    commands.spawn(system);
-    
-    // Set initial state to Paused
-    next_state.set(GameState::Paused);
-    info!("Set game state to Paused");
-}
-
-fn setup_planning_phase() {
-    info!("Starting planning phase");
-}
-
-fn setup_execution_phase() {
-    info!("Starting execution phase");
-}
-
-fn update_planning_phase(
-    mut next_state: ResMut<NextState<GameState>>,
-    resources: Res<GameResources>,
-    query: Query<&SystemGraph>,
-) {
-    // Debug info
-    if let Ok(system) = query.get_single() {
-        info!(
-            "System Status - Nodes: {}, Edges: {}, Money: ${:.2}, Sprint: {}",
-            system.graph.node_count(),
-            system.graph.edge_count(),
-            resources.money,
-            resources.sprint
-        );
-    }
-    
-    // TODO: Add a way to transition to execution phase (e.g., button press)
-    // For now, let's automatically transition after 5 seconds
-    if resources.sprint == 1 {  // Only in first sprint for testing
-        next_state.set(GameState::Running);
+// Use DEVOPS_ENTROPY_SEED to replay a run, otherwise pick a fresh seed.
+// DEVOPS_ENTROPY_ARCHITECTURE picks the starting template by name.
+fn simulation_config() -> SimulationConfig {
+    let seed = std::env::var("DEVOPS_ENTROPY_SEED")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(rand::random);
+    let mut config = SimulationConfig::with_seed(seed);
+    if let Ok(architecture) = std::env::var("DEVOPS_ENTROPY_ARCHITECTURE") {
+        config.architecture = architecture;
     }
+    config
 }
 
-fn update_execution_phase(
-    mut next_state: ResMut<NextState<GameState>>,
-    mut execution_phase: ResMut<ExecutionPhase>,
-    time: Res<Time>,
-    resources: Res<GameResources>,
-) {
-    // Update elapsed time
-    execution_phase.elapsed_time += time.delta_seconds_f64();
-    
-    // Print debug info every second
-    if execution_phase.elapsed_time.floor() > (execution_phase.elapsed_time - time.delta_seconds_f64()).floor() {
-        info!(
-            "Execution Phase - Step: {}/{}, Money: ${:.2}, Time: {:.1}s",
-            execution_phase.current_step + 1,
-            execution_phase.steps.len(),
-            resources.money,
-            execution_phase.elapsed_time
-        );
-    }
-    
-    // After 10 seconds, go back to planning
-    if execution_phase.elapsed_time >= 10.0 {
-        execution_phase.elapsed_time = 0.0;
-        next_state.set(GameState::Planning);
-        info!("Execution phase complete, returning to planning");
+// Built-in architectures plus whatever designers dropped into the assets folder
+fn architecture_templates() -> TemplateLibrary {
+    let mut library = TemplateLibrary::builtin();
+    for error in library.load_dir(TEMPLATE_DIR) {
+        warn!("Skipped architecture template: {}", error);
     }
+    info!("Architectures available: {}", library.names().collect::<Vec<_>>().join(", "));
+    library
 }
 
 fn handle_window_close(
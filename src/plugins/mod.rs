@@ -1,3 +1,5 @@
 pub mod ui;
+pub mod simulation;
 
-pub use ui::UiPlugin; 
\ No newline at end of file
+pub use ui::UiPlugin;
+pub use simulation::SimulationPlugin; 
\ No newline at end of file
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use crate::cards::{Deck, ActiveEffects};
+use crate::components::TemplateLibrary;
+use crate::resources::{
+    GameResources, ExecutionPhase, PlanningTimer, PlannedChanges, FailureLog, LatencyLog,
+    CustomerModel, TrafficLog, DefectTracker, SprintBaseline, SprintHistory, SprintReport,
+    SimulationConfig, SimRng, ArchitectureMigration, GraphHistory,
+};
+use crate::systems::{
+    tick_system, failure_system, start_failure_log, latency_system, start_latency_log,
+    revenue_system, start_traffic_log, defect_system, start_defect_tracking,
+    draw_hand, expire_effects,
+    open_planned_changes, freeze_planned_changes, apply_planned_changes, advance_migration,
+    setup_game, setup_planning_phase, setup_execution_phase,
+    update_planning_phase, update_execution_phase, sprint_in_progress, check_end_conditions,
+    capture_sprint_baseline, report_sprint, advance_sprint,
+    start_graph_history, record_graph_history,
+};
+use crate::GameState;
+
+// Everything the game needs to play out sprints, without any windowing or UI.
+// The windowed game adds this next to `DefaultPlugins` and `UiPlugin`;
+// headless runs add it next to `MinimalPlugins`.
+#[derive(Default)]
+pub struct SimulationPlugin {
+    pub config: SimulationConfig,
+}
+
+impl SimulationPlugin {
+    pub fn with_seed(seed: u64) -> Self {
+        Self { config: SimulationConfig::with_seed(seed) }
+    }
+}
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<GameState>()
+            .init_resource::<ExecutionPhase>()
+            .init_resource::<GameResources>()
+            .init_resource::<Deck>()
+            .init_resource::<ActiveEffects>()
+            .init_resource::<PlannedChanges>()
+            .init_resource::<FailureLog>()
+            .init_resource::<LatencyLog>()
+            .init_resource::<CustomerModel>()
+            .init_resource::<TrafficLog>()
+            .init_resource::<DefectTracker>()
+            .init_resource::<PlanningTimer>()
+            .init_resource::<TemplateLibrary>()
+            .init_resource::<SprintBaseline>()
+            .init_resource::<SprintHistory>()
+            .init_resource::<ArchitectureMigration>()
+            .init_resource::<GraphHistory>()
+            .add_event::<SprintReport>()
+            .insert_resource(FixedTime::new(self.config.tick_period()))
+            .insert_resource(SimRng::from_config(&self.config))
+            .insert_resource(self.config.clone())
+            .add_systems(Startup, setup_game)
+            .add_systems(OnEnter(GameState::Planning), (
+                setup_planning_phase,
+                expire_effects,
+                draw_hand,
+                open_planned_changes,
+                start_graph_history,
+            ))
+            .add_systems(OnExit(GameState::Planning), (
+                freeze_planned_changes,
+                start_failure_log,
+                start_latency_log,
+                start_traffic_log,
+                start_defect_tracking,
+                capture_sprint_baseline,
+            ))
+            .add_systems(OnEnter(GameState::Running), (
+                setup_execution_phase,
+                apply_planned_changes,
+                advance_migration.after(apply_planned_changes),
+            ))
+            .add_systems(OnExit(GameState::SprintReview), advance_sprint)
+            .add_systems(Update, update_planning_phase.run_if(in_state(GameState::Planning)))
+            // The simulation advances in fixed ticks so outcomes don't depend on frame rate
+            .add_systems(FixedUpdate, (
+                tick_system,
+                failure_system,
+                latency_system,
+                defect_system,
+                revenue_system,
+                update_execution_phase,
+                report_sprint,
+                record_graph_history,
+                check_end_conditions,
+            ).chain()
+                .run_if(in_state(GameState::Running).and_then(sprint_in_progress)));
+    }
+}
+
+// Easter egg: "Runs in CI, on a Raspberry Pi, and allegedly on the coffee machine ☕"
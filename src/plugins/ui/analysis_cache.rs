@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use crate::components::{SystemGraph, GraphAnalysis, is_declared_entry_point, is_revenue_sink};
+
+// Dependency analysis of the live system, shared by the status window and the
+// graph view. It only depends on which nodes and edges exist, so it's redone
+// when those change rather than every frame.
+#[derive(Resource, Default)]
+pub struct AnalysisCache {
+    structure: Option<Structure>,
+    analysis: GraphAnalysis,
+}
+
+// Node names, with whether they declare themselves an entry point or a revenue
+// sink since that decides where traffic enters, and edges by endpoint name
+type Structure = (Vec<(String, bool, bool)>, Vec<(String, String)>);
+
+impl AnalysisCache {
+    pub fn analysis(&self) -> &GraphAnalysis {
+        &self.analysis
+    }
+
+    // Re-run the analysis if the structure changed. Returns whether it did.
+    pub fn update(&mut self, system: &SystemGraph) -> bool {
+        let structure = structure(system);
+        if self.structure.as_ref() == Some(&structure) {
+            return false;
+        }
+        self.analysis = GraphAnalysis::of(system);
+        self.structure = Some(structure);
+        true
+    }
+}
+
+fn structure(system: &SystemGraph) -> Structure {
+    let graph = &system.graph;
+    let nodes = graph.node_weights()
+        .map(|node| (node.name.clone(), is_declared_entry_point(node), is_revenue_sink(node)))
+        .collect();
+    let edges = graph.edge_indices()
+        .filter_map(|idx| graph.edge_endpoints(idx))
+        .map(|(from, to)| (graph[from].name.clone(), graph[to].name.clone()))
+        .collect();
+    (nodes, edges)
+}
+
+pub fn refresh_analysis(query: Query<&SystemGraph>, mut cache: ResMut<AnalysisCache>) {
+    if let Ok(system) = query.get_single() {
+        cache.update(system);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::SystemEdge;
+    use crate::test_utils::create_test_graph;
+
+    #[test]
+    fn test_only_structural_changes_rerun_the_analysis() {
+        let mut system = create_test_graph();
+        let mut cache = AnalysisCache::default();
+        assert!(cache.update(&system));
+        assert_eq!(cache.analysis().node("test_node_2").unwrap().blast_radius, vec!["test_node_1"]);
+
+        system.node_mut("test_node_1").unwrap().health = 10.0;
+        assert!(!cache.update(&system));
+        // Entry points come from the topology, never from the critical path flags
+        system.node_mut("test_node_2").unwrap().critical_path = true;
+        assert!(!cache.update(&system));
+        system.node_mut("test_node_2").unwrap().node_type = "gateway".into();
+        assert!(cache.update(&system));
+
+        system.add_edge("test_node_2", "test_node_1", SystemEdge::default());
+        assert!(cache.update(&system));
+        assert!(cache.analysis().node("test_node_1").unwrap().in_cycle);
+    }
+}
+
+// Easter egg: "There are two hard problems in computer science, and this file has one of them 🗃️"
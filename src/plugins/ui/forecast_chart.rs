@@ -0,0 +1,138 @@
+use bevy_egui::egui;
+use bevy_egui::egui::plot::{Plot, Line, Polygon, PlotPoints};
+use crate::forecast::{forecast, Forecast, ForecastParams, ConfidenceBand, SprintForecast, LiveGame};
+use crate::cards::ActiveEffects;
+use crate::components::{SystemGraph, GraphDiff};
+use crate::resources::{SimulationConfig, GameResources, DefectTracker, ArchitectureMigration};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ForecastMetric {
+    #[default]
+    Money,
+    Reputation,
+    TechDebt,
+    NodeHealth(String),
+}
+
+impl ForecastMetric {
+    fn name(&self) -> String {
+        match self {
+            Self::Money => "💰 Money".into(),
+            Self::Reputation => "⭐ Reputation".into(),
+            Self::TechDebt => "🔥 Avg Tech Debt".into(),
+            Self::NodeHealth(node) => format!("❤️ {} health", node),
+        }
+    }
+
+    fn band(&self, sprint: &SprintForecast) -> Option<ConfidenceBand> {
+        match self {
+            Self::Money => Some(sprint.money),
+            Self::Reputation => Some(sprint.reputation),
+            Self::TechDebt => Some(sprint.average_tech_debt),
+            Self::NodeHealth(node) => sprint.node_health.get(node).copied(),
+        }
+    }
+}
+
+// The last forecast run from the planning panel, the game it started from
+// and what it's charting
+#[derive(Default)]
+pub struct ForecastView {
+    forecast: Option<Forecast>,
+    resources: GameResources,
+    defects: DefectTracker,
+    migration: ArchitectureMigration,
+    effects: ActiveEffects,
+    system: Option<SystemGraph>,
+    metric: ForecastMetric,
+}
+
+impl ForecastView {
+    // A forecast no longer starts from where we are once the game has moved
+    // on, whether by a sprint, a quick-load or a change to the system
+    fn is_stale(&self, game: LiveGame) -> bool {
+        self.resources != *game.resources
+            || self.defects != *game.defects
+            || self.migration != *game.migration
+            || self.effects != *game.effects
+            || self.system.as_ref().is_none_or(|cached| !GraphDiff::between(cached, game.system).is_empty())
+    }
+}
+
+// Fan chart of where the next sprints are likely to land if nothing else is played
+pub fn show_forecast(
+    ui: &mut egui::Ui,
+    view: &mut ForecastView,
+    game: LiveGame,
+    config: &SimulationConfig,
+) {
+    if view.forecast.is_some() && view.is_stale(game) {
+        view.forecast = None;
+    }
+
+    let params = ForecastParams::default();
+    ui.horizontal(|ui| {
+        if ui.button("🔮 Forecast").clicked() {
+            view.forecast = Some(forecast(game, config, &params));
+            view.resources = game.resources.clone();
+            view.defects = game.defects.clone();
+            view.migration = game.migration.clone();
+            view.effects = game.effects.clone();
+            view.system = Some(game.system.clone());
+        }
+        ui.label(format!("{} runs, {} sprints ahead", params.runs, params.sprints));
+    });
+    let Some(forecast) = &view.forecast else {
+        return;
+    };
+
+    egui::ComboBox::from_id_source("forecast_metric")
+        .selected_text(view.metric.name())
+        .show_ui(ui, |ui| {
+            let mut metrics = vec![ForecastMetric::Money, ForecastMetric::Reputation, ForecastMetric::TechDebt];
+            if let Some(now) = forecast.sprints.first() {
+                metrics.extend(now.node_health.keys().cloned().map(ForecastMetric::NodeHealth));
+            }
+            for metric in metrics {
+                let name = metric.name();
+                ui.selectable_value(&mut view.metric, metric, name);
+            }
+        });
+
+    let points: Vec<(f64, ConfidenceBand)> = forecast.sprints.iter()
+        .filter_map(|sprint| view.metric.band(sprint).map(|band| (sprint.sprint as f64, band)))
+        .collect();
+    let color = egui::Color32::from_rgb(100, 150, 255);
+    Plot::new("forecast_fan_chart")
+        .height(160.0)
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .show(ui, |plot| {
+            plot.polygon(band_area(&points, |band| band.p5, |band| band.p95).color(color).fill_alpha(0.15));
+            plot.polygon(band_area(&points, |band| band.p25, |band| band.p75).color(color).fill_alpha(0.3));
+            let median: PlotPoints = points.iter().map(|(x, band)| [*x, band.p50]).collect();
+            plot.line(Line::new(median).color(color).width(2.0).name("median"));
+        });
+
+    if let Some(last) = forecast.sprints.last() {
+        if last.finished > 0.0 {
+            ui.colored_label(egui::Color32::from_rgb(255, 165, 0),
+                format!("⚠️ The game ends by sprint {} in {:.0}% of runs", last.sprint, last.finished * 100.0));
+        }
+    }
+}
+
+// Closed outline running along the lower edge of a band and back along the upper one
+fn band_area(
+    points: &[(f64, ConfidenceBand)],
+    lower: fn(&ConfidenceBand) -> f64,
+    upper: fn(&ConfidenceBand) -> f64,
+) -> Polygon {
+    let outline: PlotPoints = points.iter().map(|(x, band)| [*x, lower(band)])
+        .chain(points.iter().rev().map(|(x, band)| [*x, upper(band)]))
+        .collect();
+    Polygon::new(outline)
+}
+
+// Easter egg: "The cone of uncertainty, rendered in soothing blue 🍦"
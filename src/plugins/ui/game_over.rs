@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use bevy::app::AppExit;
+use bevy_egui::{egui, EguiContexts};
+use crate::resources::FinalScore;
+
+// The final score breakdown, shown once the game is won or lost
+pub fn show_final_score(
+    mut contexts: EguiContexts,
+    score: Option<Res<FinalScore>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let Some(score) = score else {
+        return;
+    };
+    let title = if score.reason.is_victory() { "Victory 🏆" } else { "Game Over 💀" };
+    egui::Window::new(title)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            if show_final_score_ui(ui, &score) {
+                exit.send(AppExit);
+            }
+        });
+}
+
+// Returns true when the player asks to quit
+fn show_final_score_ui(ui: &mut egui::Ui, score: &FinalScore) -> bool {
+    ui.heading(score.reason.title());
+    ui.label(score.reason.describe());
+    ui.add_space(8.0);
+    ui.separator();
+    ui.add_space(8.0);
+
+    egui::Grid::new("final_score").striped(true).show(ui, |ui| {
+        for line in &score.lines {
+            ui.label(line.label);
+            ui.label(&line.detail);
+            ui.label(format!("{:+.0}", line.points));
+            ui.end_row();
+        }
+    });
+    ui.add_space(8.0);
+    ui.separator();
+    ui.heading(format!("Final score: {:.0}", score.total()));
+    ui.label(format!("Game ended after sprint {}", score.sprints));
+    ui.add_space(8.0);
+
+    ui.button("🚪 Quit").clicked()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::{EndReason, GameResources, DefectTracker};
+    use crate::test_utils::create_test_graph;
+
+    // Lays the score out for one frame with the given input; returns whether
+    // the player quit and every piece of text drawn
+    fn run_frame(ctx: &egui::Context, score: &FinalScore, events: Vec<egui::Event>) -> (bool, Vec<(String, egui::Rect)>) {
+        let mut quit = false;
+        let output = ctx.run(egui::RawInput { events, ..Default::default() }, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| quit = show_final_score_ui(ui, score));
+        });
+        let texts = output.shapes.into_iter()
+            .filter_map(|clipped| match clipped.1 {
+                egui::Shape::Text(text) => Some((text.galley.text().to_string(), text.visual_bounding_rect())),
+                _ => None,
+            })
+            .collect();
+        (quit, texts)
+    }
+
+    #[test]
+    fn test_final_score_ui_shows_the_breakdown_and_quits() {
+        let score = FinalScore::new(
+            EndReason::Survived, &GameResources::default(), &create_test_graph(), &DefectTracker::default(),
+        );
+        let ctx = egui::Context::default();
+        // The grid sizes its columns on the first frame and draws on the second
+        run_frame(&ctx, &score, vec![]);
+        let (quit, texts) = run_frame(&ctx, &score, vec![]);
+        assert!(!quit);
+        let shown = |wanted: &str| texts.iter().any(|(text, _)| text == wanted);
+        assert!(shown(score.reason.title()));
+        assert!(shown(&format!("Final score: {:.0}", score.total())));
+        for line in &score.lines {
+            assert!(shown(line.label), "{} is missing", line.label);
+        }
+
+        let quit_button = texts.iter().find(|(text, _)| text == "🚪 Quit").expect("quit button").1.center();
+        let press = |pressed| egui::Event::PointerButton {
+            pos: quit_button, button: egui::PointerButton::Primary, pressed, modifiers: Default::default(),
+        };
+        run_frame(&ctx, &score, vec![egui::Event::PointerMoved(quit_button), press(true)]);
+        assert!(run_frame(&ctx, &score, vec![press(false)]).0);
+    }
+}
+
+// Easter egg: "Insert coin to refactor 🪙"
@@ -1,23 +1,73 @@
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
-use crate::components::SystemGraph;
-use petgraph::visit::EdgeRef;
-use crate::resources::GameResources;
+use crate::components::{SystemGraph, GraphAnalysis};
+use super::analysis_cache::AnalysisCache;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+// What the node colours show: health by default, or one of the dependency analyses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GraphHighlight {
+    #[default]
+    Health,
+    SinglePointsOfFailure,
+    BlastRadius,
+    Centrality,
+    Cycles,
+}
+
+impl GraphHighlight {
+    pub const ALL: [GraphHighlight; 5] = [
+        GraphHighlight::Health,
+        GraphHighlight::SinglePointsOfFailure,
+        GraphHighlight::BlastRadius,
+        GraphHighlight::Centrality,
+        GraphHighlight::Cycles,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GraphHighlight::Health => "Health",
+            GraphHighlight::SinglePointsOfFailure => "Single points of failure",
+            GraphHighlight::BlastRadius => "Blast radius",
+            GraphHighlight::Centrality => "Centrality",
+            GraphHighlight::Cycles => "Cycles",
+        }
+    }
+}
+
+const NODE_RADIUS: f32 = 20.0;
 
 pub fn show_graph(
     mut contexts: EguiContexts,
     query: Query<&SystemGraph>,
+    cache: Res<AnalysisCache>,
+    mut highlight: Local<GraphHighlight>,
 ) {
     if let Ok(system) = query.get_single() {
         egui::Window::new("System Graph")
             .default_pos([300.0, 20.0])
             .show(contexts.ctx_mut(), |ui| {
-                show_graph_ui(ui, system);
+                ui.horizontal(|ui| {
+                    ui.label("Highlight:");
+                    egui::ComboBox::from_id_source("graph_highlight")
+                        .selected_text(highlight.name())
+                        .show_ui(ui, |ui| {
+                            for mode in GraphHighlight::ALL {
+                                ui.selectable_value(&mut *highlight, mode, mode.name());
+                            }
+                        });
+                });
+                if *highlight == GraphHighlight::BlastRadius {
+                    ui.label(egui::RichText::new("Hover a component to see what goes down with it").small());
+                }
+                let analysis = (*highlight != GraphHighlight::Health).then(|| (*highlight, cache.analysis()));
+                show_graph_ui(ui, system, analysis);
             });
     }
 }
 
-fn show_graph_ui(ui: &mut egui::Ui, system: &SystemGraph) {
+// Nodes are coloured by health, or by one of the analyses when a highlight is given
+pub(super) fn show_graph_ui(ui: &mut egui::Ui, system: &SystemGraph, highlight: Option<(GraphHighlight, &GraphAnalysis)>) {
     // Add padding and frame for graph
     egui::Frame::dark_canvas(ui.style())
         .inner_margin(egui::style::Margin::same(20.0))
@@ -42,6 +92,12 @@ fn show_graph_ui(ui: &mut egui::Ui, system: &SystemGraph) {
                 );
                 node_positions.insert(node_idx, pos);
             }
+
+            let hovered = response.hover_pos().and_then(|pointer| {
+                node_positions.iter()
+                    .find(|(_, pos)| pos.distance(pointer) <= NODE_RADIUS)
+                    .map(|(idx, _)| system.graph[*idx].name.as_str())
+            });
             
             // Draw edges first (so they're behind nodes)
             for edge in system.graph.edge_references() {
@@ -51,7 +107,7 @@ fn show_graph_ui(ui: &mut egui::Ui, system: &SystemGraph) {
                 ) {
                     // Calculate arrow points
                     let dir = (end - start).normalized();
-                    let node_radius = 20.0;
+                    let node_radius = NODE_RADIUS;
                     let arrow_start = start + dir * node_radius;
                     let arrow_end = end - dir * node_radius;
                     
@@ -101,19 +157,21 @@ fn show_graph_ui(ui: &mut egui::Ui, system: &SystemGraph) {
             for (node_idx, pos) in &node_positions {
                 let node = &system.graph[*node_idx];
                 
-                // Node color based on health
-                let node_color = if node.health > 75.0 {
-                    egui::Color32::from_rgb(100, 200, 100)
-                } else if node.health > 50.0 {
-                    egui::Color32::from_rgb(200, 200, 100)
-                } else {
-                    egui::Color32::from_rgb(200, 100, 100)
-                };
+                // Node color based on health, unless an analysis is highlighted
+                let node_color = highlight
+                    .map(|(highlight, analysis)| highlight_color(highlight, analysis, &node.name, hovered))
+                    .unwrap_or(if node.health > 75.0 {
+                        egui::Color32::from_rgb(100, 200, 100)
+                    } else if node.health > 50.0 {
+                        egui::Color32::from_rgb(200, 200, 100)
+                    } else {
+                        egui::Color32::from_rgb(200, 100, 100)
+                    });
                 
                 // Draw node shadow
                 painter.circle(
                     *pos + egui::vec2(2.0, 2.0),
-                    20.0,
+                    NODE_RADIUS,
                     egui::Color32::from_black_alpha(100),
                     egui::Stroke::NONE,
                 );
@@ -121,7 +179,7 @@ fn show_graph_ui(ui: &mut egui::Ui, system: &SystemGraph) {
                 // Draw node circle
                 painter.circle(
                     *pos,
-                    20.0,
+                    NODE_RADIUS,
                     node_color,
                     egui::Stroke::new(2.0, egui::Color32::WHITE),
                 );
@@ -154,23 +212,38 @@ fn show_graph_ui(ui: &mut egui::Ui, system: &SystemGraph) {
         });
 }
 
-const MIN_NODE_DISTANCE: f32 = 40.0;  // Minimum distance between nodes
-
-fn calculate_node_positions(system: &SystemGraph) -> Vec<egui::Pos2> {
-    let node_count = system.graph.node_count();
-    let radius = 200.0;
-    let center = egui::pos2(300.0, 300.0);
-    
-    system.graph.node_indices()
-        .enumerate()
-        .map(|(i, _)| {
-            let angle = (i as f32 * 2.0 * std::f32::consts::PI) / node_count as f32;
-            egui::pos2(
-                center.x + radius * angle.cos(),
-                center.y + radius * angle.sin(),
+fn highlight_color(
+    highlight: GraphHighlight,
+    analysis: &GraphAnalysis,
+    name: &str,
+    hovered: Option<&str>,
+) -> egui::Color32 {
+    let dim = egui::Color32::from_rgb(90, 90, 100);
+    let Some(node) = analysis.node(name) else {
+        return dim;
+    };
+    match highlight {
+        GraphHighlight::Health => dim,
+        GraphHighlight::SinglePointsOfFailure if node.single_point_of_failure => egui::Color32::from_rgb(230, 80, 80),
+        GraphHighlight::Cycles if node.in_cycle => egui::Color32::from_rgb(230, 200, 60),
+        GraphHighlight::SinglePointsOfFailure | GraphHighlight::Cycles => dim,
+        GraphHighlight::BlastRadius => match hovered {
+            Some(source) if source == name => egui::Color32::from_rgb(240, 150, 40),
+            Some(source) if analysis.node(source).is_some_and(|s| s.blast_radius.iter().any(|n| n == name)) => {
+                egui::Color32::from_rgb(230, 80, 80)
+            }
+            _ => dim,
+        },
+        GraphHighlight::Centrality => {
+            let max = analysis.max_centrality();
+            let share = if max > 0.0 { (node.centrality / max) as f32 } else { 0.0 };
+            egui::Color32::from_rgb(
+                (90.0 + 110.0 * share) as u8,
+                (90.0 - 30.0 * share) as u8,
+                (100.0 + 140.0 * share) as u8,
             )
-        })
-        .collect()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -182,7 +255,7 @@ mod tests {
     fn create_test_system() -> SystemGraph {
         let mut graph = SystemGraph::new();
         
-        let node1 = graph.add_node(SystemNode {
+        graph.add_node(SystemNode {
             name: "test_node_1".into(),
             health: 100.0,
             tech_debt: 10.0,
@@ -191,7 +264,7 @@ mod tests {
             ..Default::default()
         });
         
-        let node2 = graph.add_node(SystemNode {
+        graph.add_node(SystemNode {
             name: "test_node_2".into(),
             health: 100.0,
             tech_debt: 0.0,
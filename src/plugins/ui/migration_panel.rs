@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::components::{ArchitectureType, SystemGraph};
+use crate::resources::{
+    GameResources, ArchitectureMigration, SimulationConfig, DEFAULT_MIGRATION_SPRINTS, is_strangled,
+};
+
+// Strangler-fig migrations: start one during planning, then watch it progress sprint by sprint
+pub fn show_migration_panel(
+    mut contexts: EguiContexts,
+    resources: Res<GameResources>,
+    config: Res<SimulationConfig>,
+    mut migration: ResMut<ArchitectureMigration>,
+    query: Query<&SystemGraph>,
+) {
+    egui::Window::new("Architecture Migration 🌿")
+        .default_pos([600.0, 520.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(format!("🏛️ Current architecture: {}", resources.current_architecture.name()));
+            ui.add_space(8.0);
+
+            if let Some(plan) = migration.plan() {
+                ui.label(format!("Migrating to {}: {}/{} rollout sprints done",
+                    plan.target.name(), plan.completed, plan.sprints));
+                if let Ok(system) = query.get_single() {
+                    let strangled: Vec<&str> = system.graph.node_weights()
+                        .filter(|node| is_strangled(node))
+                        .map(|node| node.name.as_str())
+                        .collect();
+                    if !strangled.is_empty() {
+                        ui.label(format!("🔁 Dual-running: {}", strangled.join(", ")));
+                        let penalty = ArchitectureMigration::dual_running_cost(system) * config.sprint_duration_secs;
+                        ui.colored_label(egui::Color32::from_rgb(255, 165, 0),
+                            format!("💸 Dual-running overhead: ~${:.0} this sprint", penalty));
+                    }
+                }
+                return;
+            }
+
+            ui.label(format!("Replace the system piece by piece over {} sprints.", DEFAULT_MIGRATION_SPRINTS));
+            ui.label("Legacy nodes keep running, at extra cost, until their replacement takes over.");
+            let mut chosen = None;
+            for target in ArchitectureType::ALL {
+                if resources.current_architecture != target.into() && ui.button(format!("Migrate to {}", target.name())).clicked() {
+                    chosen = Some(target);
+                }
+            }
+            if let Some(target) = chosen {
+                match migration.start(&resources.current_architecture, target, DEFAULT_MIGRATION_SPRINTS) {
+                    Ok(()) => info!("Started migrating to {}", target.name()),
+                    Err(err) => warn!("Could not start migration: {}", err),
+                }
+            }
+        });
+}
+
+// Easter egg: "Phase 2 of the rewrite starts right after phase 1 finishes. Any decade now 🏗️"
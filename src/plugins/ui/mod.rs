@@ -1,13 +1,18 @@
+mod analysis_cache;
 mod graph_view;
 mod system_status;
 mod planning_panel;
+mod forecast_chart;
+mod preview_panel;
+mod timeline;
+mod migration_panel;
+mod game_over;
+mod sprint_report;
 #[cfg(test)]
 mod test_utils;
 
 use bevy::prelude::*;
 use bevy_egui::EguiPlugin;
-use crate::resources::GameResources;
-use crate::components::SystemGraph;
 use crate::GameState;
 
 pub struct UiPlugin;
@@ -15,12 +20,27 @@ pub struct UiPlugin;
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(EguiPlugin)
+           .init_resource::<analysis_cache::AnalysisCache>()
            .add_systems(Update, (
+               analysis_cache::refresh_analysis.before(graph_view::show_graph).before(system_status::show_system_status),
                graph_view::show_graph,
                system_status::show_system_status,
-               planning_panel::show_planning_panel,
-           ).run_if(not(in_state(GameState::Loading))));
+               timeline::show_timeline,
+           ).run_if(not(in_state(GameState::Loading))))
+           .add_systems(Update, planning_panel::show_planning_panel.run_if(game_in_progress))
+           .add_systems(Update, migration_panel::show_migration_panel.run_if(in_state(GameState::Planning)))
+           .add_systems(Update, preview_panel::show_change_preview.run_if(in_state(GameState::Planning)))
+           .add_systems(Update, sprint_report::show_sprint_report.run_if(in_state(GameState::SprintReview)))
+           .add_systems(Update, game_over::show_final_score.run_if(game_finished));
     }
 }
 
+fn game_in_progress(state: Res<State<GameState>>) -> bool {
+    matches!(state.get(), GameState::Planning | GameState::Running | GameState::Paused)
+}
+
+fn game_finished(state: Res<State<GameState>>) -> bool {
+    state.get().is_finished()
+}
+
 // Easter egg: "This UI plugin was assembled with care and a dash of whimsy 🎨" 
\ No newline at end of file
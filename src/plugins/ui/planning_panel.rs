@@ -1,15 +1,40 @@
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
-use crate::resources::GameResources;
-use crate::components::SystemGraph;
+use crate::resources::{
+    GameResources, PlannedChanges, PlannedAction, PlanningTimer, SimulationConfig, DefectTracker, ArchitectureMigration,
+};
+use crate::components::{SystemGraph, GraphChange};
+use crate::cards::{Deck, DevOpsCard, RestructureKind, ActiveEffects};
+use crate::forecast::LiveGame;
 use crate::GameState;
+use petgraph::Direction;
+use super::forecast_chart::{show_forecast, ForecastView};
 
+// Nodes picked as targets for structural cards, plus one of the primary's
+// dependencies for cards that rewire an edge
+#[derive(Default)]
+pub struct RestructureTargets {
+    primary: String,
+    secondary: String,
+    dependency: String,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn show_planning_panel(
     mut contexts: EguiContexts,
     mut next_state: ResMut<NextState<GameState>>,
     resources: Res<GameResources>,
+    defects: Res<DefectTracker>,
+    migration: Res<ArchitectureMigration>,
+    effects: Res<ActiveEffects>,
+    mut deck: ResMut<Deck>,
+    mut planned: ResMut<PlannedChanges>,
     query: Query<&SystemGraph>,
     state: Res<State<GameState>>,
+    timer: Res<PlanningTimer>,
+    config: Res<SimulationConfig>,
+    mut targets: Local<RestructureTargets>,
+    mut forecast_view: Local<ForecastView>,
 ) {
     egui::Window::new("Planning Phase 🎯")
         .default_pos([600.0, 20.0])
@@ -19,6 +44,11 @@ pub fn show_planning_panel(
             
             // Sprint info
             ui.label(format!("Sprint {} Planning", resources.sprint));
+            if *state.get() == GameState::Planning {
+                if let Some(remaining) = timer.remaining(config.planning_time_limit_secs) {
+                    ui.label(format!("⏳ {:.0}s left to plan", remaining.ceil()));
+                }
+            }
             ui.add_space(16.0);
             
             // Available resources
@@ -42,9 +72,111 @@ pub fn show_planning_panel(
                         .sum::<f64>() / system.graph.node_count() as f64;
                     ui.label(format!("🔥 Avg Tech Debt: {:.1}%", avg_tech_debt));
                 });
+                ui.add_space(8.0);
+                ui.collapsing("🔮 Outlook", |ui| {
+                    let game = LiveGame {
+                        system,
+                        resources: &resources,
+                        defects: &defects,
+                        migration: &migration,
+                        effects: &effects,
+                    };
+                    show_forecast(ui, &mut forecast_view, game, &config);
+                });
             }
             ui.add_space(16.0);
-            
+
+            // Targets for structural cards
+            if let Ok(system) = query.get_single() {
+                let mut names: Vec<&String> = system.node_indices.keys().collect();
+                names.sort();
+                ui.horizontal(|ui| {
+                    ui.label("🎯 Target:");
+                    node_picker(ui, "primary_target", &names, &mut targets.primary);
+                    ui.label("with");
+                    node_picker(ui, "secondary_target", &names, &mut targets.secondary);
+                });
+                if let Some(&primary) = system.node_indices.get(&targets.primary) {
+                    let mut dependencies: Vec<&String> = system.graph
+                        .neighbors_directed(primary, Direction::Outgoing)
+                        .map(|idx| &system.graph[idx].name)
+                        .collect();
+                    dependencies.sort();
+                    ui.horizontal(|ui| {
+                        ui.label(format!("🔗 Dependency: {} ->", targets.primary));
+                        node_picker(ui, "dependency_target", &dependencies, &mut targets.dependency);
+                    });
+                }
+                ui.add_space(8.0);
+            }
+
+            // Current hand
+            let can_plan = *state.get() == GameState::Planning && planned.is_open();
+            let points_left = resources.devops_points.saturating_sub(planned.total_cost());
+            let mut to_queue = None;
+            ui.group(|ui| {
+                ui.label(format!("🃏 Your Hand ({} in draw pile, {} discarded):",
+                    deck.draw_pile.len(), deck.discard_pile.len()));
+                if deck.hand.is_empty() {
+                    ui.label("No cards in hand");
+                }
+                let mut available: Vec<String> = deck.played.clone();
+                available.extend(planned.queued_cards().map(String::from));
+                for (i, card) in deck.hand.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        let playable = can_plan
+                            && card.cost <= points_left
+                            && card.prerequisites_met(&available);
+                        if ui.add_enabled(playable, egui::Button::new("Queue")).clicked() {
+                            to_queue = Some(i);
+                        }
+                        ui.label(egui::RichText::new(format!("{} {} (Cost: {})",
+                            card.card_type.icon(), card.name, card.cost)).strong());
+                    });
+                    ui.label(egui::RichText::new(&card.description).italics());
+                    if !card.prerequisites.is_empty() {
+                        ui.label(format!("Requires: {}", card.prerequisites.join(", ")));
+                    }
+                }
+            });
+            if let Some(card) = to_queue.and_then(|i| deck.take_from_hand(i)) {
+                match planned_action(card.clone(), query.get_single().ok(), &targets) {
+                    Some(action) => {
+                        if let Err(err) = planned.queue(action, resources.devops_points) {
+                            warn!("Could not queue {}: {}", card.name, err);
+                            deck.return_to_hand(card);
+                        }
+                    }
+                    None => {
+                        warn!("{} needs a valid target", card.name);
+                        deck.return_to_hand(card);
+                    }
+                }
+            }
+            ui.add_space(16.0);
+
+            // Queued changes
+            let mut to_remove = None;
+            ui.group(|ui| {
+                ui.label(format!("📋 Queued Changes ({}/{} DevOps points):",
+                    planned.total_cost(), resources.devops_points));
+                if planned.actions().is_empty() {
+                    ui.label("Nothing queued");
+                }
+                for (i, action) in planned.actions().iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(can_plan, egui::Button::new("✖")).clicked() {
+                            to_remove = Some(i);
+                        }
+                        ui.label(action.describe());
+                    });
+                }
+            });
+            if let Some(card) = to_remove.and_then(|i| planned.remove(i)).and_then(PlannedAction::into_card) {
+                deck.return_to_hand(card);
+            }
+            ui.add_space(16.0);
+
             // Action buttons with state awareness
             ui.horizontal(|ui| {
                 let current_state = state.get();
@@ -83,6 +215,70 @@ pub fn show_planning_panel(
         });
 }
 
+fn node_picker(ui: &mut egui::Ui, id: &str, names: &[&String], selected: &mut String) {
+    egui::ComboBox::from_id_source(id)
+        .selected_text(selected.clone())
+        .show_ui(ui, |ui| {
+            for name in names {
+                ui.selectable_value(selected, (*name).clone(), name.as_str());
+            }
+        });
+}
+
+// Turn a card from the hand into a queued action, building the structural
+// change from the picked targets for Architecture cards. Rerouting moves the
+// primary's edge to the picked dependency over to the secondary.
+fn planned_action(
+    card: DevOpsCard,
+    system: Option<&SystemGraph>,
+    targets: &RestructureTargets,
+) -> Option<PlannedAction> {
+    let Some(kind) = card.restructure else {
+        return Some(PlannedAction::PlayCard(card));
+    };
+    let system = system?;
+    let primary = system.node_indices.get(&targets.primary)?;
+
+    let change = match kind {
+        RestructureKind::SplitNode => {
+            let new_name = (2..)
+                .map(|n| format!("{}_{}", targets.primary, n))
+                .find(|name| !system.node_indices.contains_key(name))?;
+            // Hand every other dependency to the new half
+            let moved_neighbors = system.graph
+                .neighbors_directed(*primary, Direction::Outgoing)
+                .skip(1)
+                .step_by(2)
+                .map(|idx| system.graph[idx].name.clone())
+                .collect();
+            GraphChange::SplitNode { node: targets.primary.clone(), new_name, moved_neighbors }
+        }
+        RestructureKind::MergeNodes => {
+            if targets.primary == targets.secondary || !system.node_indices.contains_key(&targets.secondary) {
+                return None;
+            }
+            GraphChange::MergeNodes { keep: targets.primary.clone(), absorb: targets.secondary.clone() }
+        }
+        RestructureKind::RemoveNode => GraphChange::RemoveNode(targets.primary.clone()),
+        RestructureKind::RetargetEdge => {
+            let dependency = system.node_indices.get(&targets.dependency)?;
+            if system.graph.find_edge(*primary, *dependency).is_none()
+                || targets.secondary == targets.primary
+                || targets.secondary == targets.dependency
+                || !system.node_indices.contains_key(&targets.secondary)
+            {
+                return None;
+            }
+            GraphChange::RetargetEdge {
+                from: targets.primary.clone(),
+                to: targets.dependency.clone(),
+                new_to: targets.secondary.clone(),
+            }
+        }
+    };
+    Some(PlannedAction::Restructure { card, change })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,6 +303,35 @@ mod tests {
         assert_eq!(resources.sprint, 2);
     }
 
+    #[test]
+    fn test_structural_cards_use_the_picked_targets() {
+        use crate::cards::starter_deck;
+        use crate::components::{SystemNode, SystemEdge};
+
+        let mut system = SystemGraph::new();
+        for name in ["api", "db", "replica"] {
+            system.add_node(SystemNode { name: name.into(), ..Default::default() });
+        }
+        system.add_edge("api", "db", SystemEdge::default());
+        let card = |name: &str| starter_deck().into_iter().find(|c| c.name == name).unwrap();
+        let targets = RestructureTargets {
+            primary: "api".into(),
+            secondary: "replica".into(),
+            dependency: "db".into(),
+        };
+
+        let Some(PlannedAction::Restructure { change, .. }) =
+            planned_action(card("Reroute Dependency"), Some(&system), &targets) else { panic!("no reroute") };
+        assert_eq!(change, GraphChange::RetargetEdge { from: "api".into(), to: "db".into(), new_to: "replica".into() });
+        let Some(PlannedAction::Restructure { change, .. }) =
+            planned_action(card("Decommission Service"), Some(&system), &targets) else { panic!("no removal") };
+        assert_eq!(change, GraphChange::RemoveNode("api".into()));
+
+        // api doesn't depend on replica, so there's no edge to reroute
+        let targets = RestructureTargets { dependency: "replica".into(), secondary: "db".into(), ..targets };
+        assert!(planned_action(card("Reroute Dependency"), Some(&system), &targets).is_none());
+    }
+
     #[test]
     fn test_state_transitions() {
         let mut app = setup_test_app();
@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::cards::{Deck, ActiveEffects};
+use crate::components::{SystemGraph, SystemNode, GraphDelta, GraphDiff, NodeMetric};
+use crate::forecast::LiveGame;
+use crate::preview::{preview_changes, ChangePreview};
+use crate::resources::{
+    GameResources, PlannedChanges, PlannedAction, SimulationConfig, ChangeError, DefectTracker, ArchitectureMigration,
+};
+
+// Preview and everything it was computed from, so it's only rebuilt when the
+// queue changes or the game underneath it does (e.g. after a quick-load)
+#[derive(Default)]
+pub struct PreviewCache {
+    resources: GameResources,
+    defects: DefectTracker,
+    migration: ArchitectureMigration,
+    effects: ActiveEffects,
+    played: Vec<String>,
+    system: Option<SystemGraph>,
+    actions: Vec<PlannedAction>,
+    preview: Option<Result<ChangePreview, ChangeError>>,
+}
+
+impl PreviewCache {
+    fn is_stale(&self, game: LiveGame, played: &[String], actions: &[PlannedAction]) -> bool {
+        self.preview.is_none()
+            || self.actions != actions
+            || self.resources != *game.resources
+            || self.defects != *game.defects
+            || self.migration != *game.migration
+            || self.effects != *game.effects
+            || self.played != played
+            || self.system.as_ref().is_none_or(|cached| !GraphDiff::between(cached, game.system).is_empty())
+    }
+}
+
+const COLUMNS: [NodeMetric; 3] = [NodeMetric::TechDebt, NodeMetric::Health, NodeMetric::OperatingCost];
+
+// What-if: the queued changes applied to a scratch copy, next to the live system.
+// The preview runs simulations, so it's only computed while the window is open.
+#[allow(clippy::too_many_arguments)]
+pub fn show_change_preview(
+    mut contexts: EguiContexts,
+    planned: Res<PlannedChanges>,
+    deck: Res<Deck>,
+    effects: Res<ActiveEffects>,
+    resources: Res<GameResources>,
+    defects: Res<DefectTracker>,
+    migration: Res<ArchitectureMigration>,
+    config: Res<SimulationConfig>,
+    query: Query<&SystemGraph>,
+    mut cache: Local<PreviewCache>,
+) {
+    let Ok(system) = query.get_single() else {
+        return;
+    };
+
+    egui::Window::new("What-if Preview 🔍")
+        .default_pos([1000.0, 20.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let game = LiveGame {
+                system,
+                resources: &resources,
+                defects: &defects,
+                migration: &migration,
+                effects: &effects,
+            };
+            if cache.is_stale(game, &deck.played, planned.actions()) {
+                let preview = preview_changes(game, &deck.played, planned.actions(), &config);
+                *cache = PreviewCache {
+                    resources: resources.clone(),
+                    defects: defects.clone(),
+                    migration: migration.clone(),
+                    effects: effects.clone(),
+                    played: deck.played.clone(),
+                    system: Some(system.clone()),
+                    actions: planned.actions().to_vec(),
+                    preview: Some(preview),
+                };
+            }
+            match cache.preview.as_ref().expect("preview computed above") {
+                Err(err) => {
+                    ui.colored_label(egui::Color32::from_rgb(255, 100, 100), format!("❌ {}", err));
+                }
+                Ok(preview) if preview.diff.is_empty() && preview.defects_fixed == 0 => {
+                    ui.label("Queue some changes to see what they would do.");
+                }
+                Ok(preview) => show_preview(ui, system, preview),
+            }
+        });
+}
+
+fn show_preview(ui: &mut egui::Ui, system: &SystemGraph, preview: &ChangePreview) {
+    ui.colored_label(delta_color(preview.money_delta),
+        format!("💰 Forecast money this sprint: {:+.0}", preview.money_delta));
+    if preview.defects_fixed > 0 {
+        ui.label(format!("🐛 Fixes {} open defects", preview.defects_fixed));
+    }
+    ui.add_space(8.0);
+
+    egui::Grid::new("what_if_nodes").striped(true).show(ui, |ui| {
+        ui.label("Component");
+        for metric in COLUMNS {
+            ui.label(egui::RichText::new(metric.name()).strong());
+        }
+        ui.end_row();
+
+        for name in preview.diff.changed_nodes() {
+            let (Some(before), Some(after)) = (system.node(name), preview.after.node(name)) else {
+                continue;
+            };
+            ui.label(name);
+            for metric in COLUMNS {
+                metric_cell(ui, metric, before, after);
+            }
+            ui.end_row();
+        }
+    });
+
+    for change in &preview.diff.changes {
+        match change {
+            GraphDelta::NodeAdded(node) => { ui.label(format!("🆕 {}", node.name)); }
+            GraphDelta::NodeRemoved(node) => { ui.label(format!("🗑️ {}", node.name)); }
+            GraphDelta::EdgeAdded { from, to, .. } => { ui.label(format!("➕ {} -> {}", from, to)); }
+            GraphDelta::EdgeRemoved { from, to, .. } => { ui.label(format!("➖ {} -> {}", from, to)); }
+            GraphDelta::NodeUpdated { new, .. } => { ui.label(format!("✏️ {}", new.name)); }
+            GraphDelta::NodeChanged { .. } | GraphDelta::EdgeChanged { .. } | GraphDelta::EdgeUpdated { .. } => {}
+        }
+    }
+}
+
+fn metric_cell(ui: &mut egui::Ui, metric: NodeMetric, before: &SystemNode, after: &SystemNode) {
+    let (old, new) = (metric.get(before), metric.get(after));
+    if old == new {
+        ui.label(format!("{:.1}", old));
+        return;
+    }
+    // More debt or cost is bad news, more health is good news
+    let good = if metric == NodeMetric::Health { new - old } else { old - new };
+    ui.colored_label(delta_color(good), format!("{:.1} → {:.1}", old, new));
+}
+
+fn delta_color(delta: f64) -> egui::Color32 {
+    if delta >= 0.0 {
+        egui::Color32::from_rgb(100, 255, 100)
+    } else {
+        egui::Color32::from_rgb(255, 100, 100)
+    }
+}
+
+// Easter egg: "Works on my scratch copy 🧪"
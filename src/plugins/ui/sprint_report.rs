@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::resources::{SprintHistory, SprintReport};
+use crate::GameState;
+
+// The sprint review: what the last sprint did to the company, before planning the next
+pub fn show_sprint_report(
+    mut contexts: EguiContexts,
+    history: Res<SprintHistory>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(report) = history.latest() else {
+        return;
+    };
+    egui::Window::new("Sprint Review 📋")
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            show_sprint_report_ui(ui, report);
+            ui.add_space(8.0);
+            if ui.button(format!("🎯 Plan Sprint {}", report.sprint + 1)).clicked() {
+                info!("Sprint {} review closed", report.sprint);
+                next_state.set(GameState::Planning);
+            }
+        });
+}
+
+fn show_sprint_report_ui(ui: &mut egui::Ui, report: &SprintReport) {
+    ui.heading(format!("Sprint {} Report", report.sprint));
+    ui.add_space(8.0);
+
+    egui::Grid::new("sprint_report").striped(true).show(ui, |ui| {
+        ui.label("💰 Money");
+        ui.label(format!("${:.2}", report.money));
+        ui.colored_label(delta_color(report.money_delta), format!("{:+.2}", report.money_delta));
+        ui.end_row();
+
+        ui.label("⭐ Reputation");
+        ui.label(format!("{:.1}%", report.reputation));
+        ui.colored_label(delta_color(report.reputation_delta), format!("{:+.1}", report.reputation_delta));
+        ui.end_row();
+
+        ui.label("🔥 Avg Tech Debt");
+        ui.label(format!("{:.1}%", report.average_tech_debt));
+        // Debt going up is bad news
+        ui.colored_label(delta_color(-report.tech_debt_delta), format!("{:+.1}", report.tech_debt_delta));
+        ui.end_row();
+    });
+    ui.add_space(8.0);
+
+    ui.label(format!("🛒 Customer revenue: ${:.2}", report.revenue));
+    ui.label(format!("🚨 Incidents: {}", report.incidents));
+    ui.label(format!(
+        "🐛 Defects: {} found, {} fixed, {} still open",
+        report.defects_found, report.defects_fixed, report.defects_open
+    ));
+}
+
+fn delta_color(delta: f64) -> egui::Color32 {
+    if delta >= 0.0 {
+        egui::Color32::from_rgb(100, 200, 100)
+    } else {
+        egui::Color32::from_rgb(255, 100, 100)
+    }
+}
+
+// Easter egg: "Action items from the retro: 1. Have fewer action items 📝"
@@ -1,18 +1,30 @@
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
-use crate::resources::GameResources;
-use crate::components::SystemGraph;
+use crate::resources::{
+    GameResources, FailureLog, LatencyLog, TrafficLog, DefectTracker, SimulationConfig,
+};
+use crate::components::{SystemGraph, GraphAnalysis, NodeAnalysis, is_pinned_critical};
+use super::analysis_cache::AnalysisCache;
 
+#[allow(clippy::too_many_arguments)]
 pub fn show_system_status(
     mut contexts: EguiContexts,
     resources: Res<GameResources>,
+    failures: Res<FailureLog>,
+    latency: Res<LatencyLog>,
+    traffic: Res<TrafficLog>,
+    defects: Res<DefectTracker>,
+    config: Res<SimulationConfig>,
+    cache: Res<AnalysisCache>,
     query: Query<&SystemGraph>,
 ) {
     if let Ok(system) = query.get_single() {
         egui::Window::new("System Status")
             .default_pos([20.0, 20.0])
             .show(contexts.ctx_mut(), |ui| {
-                show_system_status_ui(ui, &resources, system);
+                show_system_status_ui(ui, &resources, &failures, &defects, system, cache.analysis());
+                show_traffic_ui(ui, &traffic);
+                show_latency_ui(ui, &latency, config.latency_slo_ms);
             });
     }
 }
@@ -20,7 +32,10 @@ pub fn show_system_status(
 fn show_system_status_ui(
     ui: &mut egui::Ui,
     resources: &GameResources,
+    failures: &FailureLog,
+    defects: &DefectTracker,
     system: &SystemGraph,
+    analysis: &GraphAnalysis,
 ) {
     ui.heading("System Status");
     ui.add_space(8.0);
@@ -34,6 +49,13 @@ fn show_system_status_ui(
         .color(egui::Color32::LIGHT_BLUE));
     ui.label(egui::RichText::new(format!("⭐ Reputation: {:.1}%", resources.reputation))
         .color(egui::Color32::GOLD));
+    ui.label(format!("🚨 Incidents this sprint: {}", failures.incidents.len()));
+    ui.label(format!("🐛 Open defects: {} ({} found, {} fixed this sprint)",
+        defects.open_count(), defects.found_this_sprint, defects.fixed_this_sprint));
+    for (name, secs) in failures.critical_downtime(system) {
+        ui.label(egui::RichText::new(format!("  • {} down for {:.1}s", name, secs))
+            .color(egui::Color32::LIGHT_RED));
+    }
     
     ui.add_space(8.0);
     ui.separator();
@@ -41,11 +63,22 @@ fn show_system_status_ui(
     
     // Show individual components
     ui.heading("System Components");
+    let spofs = analysis.single_points_of_failure().count();
+    if spofs > 0 || !analysis.cycles.is_empty() {
+        ui.label(egui::RichText::new(format!("🎯 {} single points of failure · 🔁 {} dependency cycles",
+            spofs, analysis.cycles.len())).color(egui::Color32::LIGHT_RED));
+    }
     ui.separator();
     
     for node in system.graph.node_weights() {
+        let shape = analysis.node(&node.name).cloned().unwrap_or_default();
+        let title = if failures.is_down(&node.name) {
+            egui::RichText::new(format!("🔥 {} (down){}", node.name, badges(&shape))).strong().color(egui::Color32::RED)
+        } else {
+            egui::RichText::new(format!("{}{}", node.name, badges(&shape))).strong()
+        };
         ui.collapsing(
-            egui::RichText::new(&node.name).strong(), 
+            title, 
             |ui| {
                 ui.add_space(4.0);
                 ui.label(format!("Type: {}", node.node_type));
@@ -73,14 +106,30 @@ fn show_system_status_ui(
                     .color(debt_color));
                 
                 ui.label(format!("Complexity: {}", node.complexity));
+                // Analytic, so it's right even before the first request is simulated
+                ui.label(format!("Latency: {:.0}ms expected, p95 {:.0}ms ({})",
+                    node.latency.mean(), node.latency.quantile(0.95), node.latency.name()));
+                let open_defects = defects.open_on(&node.name);
+                if open_defects > 0 {
+                    let worst = defects.open_defects()
+                        .filter(|d| d.origin == node.name)
+                        .map(|d| d.severity)
+                        .max()
+                        .expect("at least one open defect");
+                    ui.label(format!("{} Defects: {} open (worst: {})",
+                        worst.icon(), open_defects, worst.name()));
+                }
                 if node.critical_path {
+                    let pinned = if is_pinned_critical(node) { " (pinned)" } else { "" };
                     ui.label(
-                        egui::RichText::new("⚠️ Critical Path")
+                        egui::RichText::new(format!("⚠️ Critical Path{}", pinned))
                             .color(egui::Color32::RED)
                             .strong()
                     );
                 }
                 
+                show_dependency_ui(ui, &shape);
+                
                 // Show attributes
                 if !node.attributes.is_empty() {
                     ui.add_space(4.0);
@@ -99,6 +148,81 @@ fn show_system_status_ui(
     }
 }
 
+// Compact markers for the component's header: 🎯 single point of failure,
+// 💥 how many dependents it takes down, 🔁 part of a dependency cycle
+fn badges(shape: &NodeAnalysis) -> String {
+    let mut badges = String::new();
+    if shape.single_point_of_failure {
+        badges.push_str(" 🎯");
+    }
+    if !shape.blast_radius.is_empty() {
+        badges.push_str(&format!(" 💥{}", shape.blast_radius.len()));
+    }
+    if shape.in_cycle {
+        badges.push_str(" 🔁");
+    }
+    badges
+}
+
+fn show_dependency_ui(ui: &mut egui::Ui, shape: &NodeAnalysis) {
+    if shape.single_point_of_failure {
+        ui.label(egui::RichText::new("🎯 Single point of failure: losing it splits the system")
+            .color(egui::Color32::LIGHT_RED));
+    }
+    if !shape.blast_radius.is_empty() {
+        ui.label(format!("💥 Takes down: {}", shape.blast_radius.join(", ")));
+    }
+    if !shape.dominators.is_empty() {
+        ui.label(format!("🚪 Only reached through: {}", shape.dominators.join(" ← ")));
+    }
+    if shape.centrality > 0.0 {
+        ui.label(format!("🔀 Centrality: {:.2}", shape.centrality));
+    }
+    if shape.in_cycle {
+        ui.label(egui::RichText::new("🔁 In a dependency cycle").color(egui::Color32::YELLOW));
+    }
+}
+
+fn show_traffic_ui(ui: &mut egui::Ui, traffic: &TrafficLog) {
+    if traffic.requests() == 0 {
+        return;
+    }
+    ui.add_space(8.0);
+    ui.heading("📈 Customer Traffic");
+    ui.separator();
+    ui.label(format!("Requests: {} ok · {} slow · {} failed",
+        traffic.succeeded, traffic.slow, traffic.failed));
+    ui.label(egui::RichText::new(format!("Earned: ${:.2}", traffic.earned))
+        .color(egui::Color32::from_rgb(158, 255, 158)));
+    ui.label(egui::RichText::new(format!("Lost to failures: ${:.2}", traffic.lost))
+        .color(egui::Color32::LIGHT_RED));
+}
+
+fn show_latency_ui(ui: &mut egui::Ui, latency: &LatencyLog, slo_ms: f64) {
+    if latency.paths.is_empty() {
+        return;
+    }
+    ui.add_space(8.0);
+    ui.heading(format!("⏱️ Request Latency (SLO {:.0}ms)", slo_ms));
+    ui.separator();
+
+    for path in &latency.paths {
+        let stats = path.stats();
+        let color = if stats.p99 > slo_ms {
+            egui::Color32::RED
+        } else if stats.p95 > slo_ms * 0.8 {
+            egui::Color32::YELLOW
+        } else {
+            egui::Color32::GREEN
+        };
+        ui.label(egui::RichText::new(path.label()).small());
+        ui.label(egui::RichText::new(format!(
+            "  p50 {:.0}ms · p95 {:.0}ms · p99 {:.0}ms · {} failed",
+            stats.p50, stats.p95, stats.p99, path.failed,
+        )).color(color));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,7 +232,7 @@ mod tests {
     fn create_test_system() -> SystemGraph {
         let mut graph = SystemGraph::new();
         
-        let node1 = graph.add_node(SystemNode {
+        graph.add_node(SystemNode {
             name: "test_node_1".into(),
             health: 100.0,
             tech_debt: 10.0,
@@ -117,7 +241,7 @@ mod tests {
             ..Default::default()
         });
         
-        let node2 = graph.add_node(SystemNode {
+        graph.add_node(SystemNode {
             name: "test_node_2".into(),
             health: 100.0,
             tech_debt: 0.0,
@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 use bevy::app::PluginGroupBuilder;
-use bevy_egui::{egui, EguiContext, EguiSettings};
-use crate::resources::GameResources;
+use bevy_egui::EguiSettings;
+use crate::resources::{
+    GameResources, FailureLog, LatencyLog, TrafficLog, DefectTracker, PlanningTimer, SprintHistory,
+    SimulationConfig, ArchitectureMigration,
+};
 
 pub struct TestPlugins;
 
@@ -10,32 +13,9 @@ impl PluginGroup for TestPlugins {
         PluginGroupBuilder::start::<Self>()
             .add(bevy::log::LogPlugin::default())
             .add(bevy::core::TaskPoolPlugin::default())
-            .add(bevy::core::TypeRegistrationPlugin::default())
-            .add(bevy::core::FrameCountPlugin::default())
-            .add(bevy::time::TimePlugin::default())
-    }
-}
-
-#[derive(Resource)]
-struct MockEguiContext {
-    ctx: egui::Context,
-}
-
-impl Default for MockEguiContext {
-    fn default() -> Self {
-        Self {
-            ctx: egui::Context::default(),
-        }
-    }
-}
-
-impl bevy_egui::EguiContexts for MockEguiContext {
-    fn ctx_mut(&mut self) -> &mut egui::Context {
-        &mut self.ctx
-    }
-
-    fn ctx(&self) -> &egui::Context {
-        &self.ctx
+            .add(bevy::core::TypeRegistrationPlugin)
+            .add(bevy::core::FrameCountPlugin)
+            .add(bevy::time::TimePlugin)
     }
 }
 
@@ -44,8 +24,15 @@ pub fn setup_test_app() -> App {
     
     app.add_plugins(TestPlugins)
        .insert_resource(GameResources::default())
-       .insert_resource(EguiSettings::default())
-       .insert_non_send_resource(MockEguiContext::default());
+       .insert_resource(FailureLog::default())
+       .insert_resource(LatencyLog::default())
+       .insert_resource(TrafficLog::default())
+       .insert_resource(DefectTracker::default())
+       .insert_resource(PlanningTimer::default())
+       .insert_resource(SprintHistory::default())
+       .insert_resource(SimulationConfig::default())
+       .insert_resource(ArchitectureMigration::default())
+       .insert_resource(EguiSettings::default());
 
     app
 } 
\ No newline at end of file
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::components::SystemGraph;
+use crate::resources::GraphHistory;
+use super::graph_view::show_graph_ui;
+
+// Where the timeline is scrubbed to, and the graph rebuilt for that point
+#[derive(Default)]
+pub struct TimelineView {
+    position: usize,
+    graph: Option<(usize, usize, SystemGraph)>,  // Position and history length it was built for
+}
+
+// Scrub back through the run sprint by sprint, e.g. for a postmortem after a collapse
+pub fn show_timeline(
+    mut contexts: EguiContexts,
+    history: Res<GraphHistory>,
+    mut view: Local<TimelineView>,
+) {
+    if history.is_empty() {
+        return;
+    }
+    egui::Window::new("Timeline ⏳")
+        .default_pos([300.0, 520.0])
+        .default_size([420.0, 520.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let last = history.len() - 1;
+            view.position = view.position.min(last);
+            ui.add(egui::Slider::new(&mut view.position, 0..=last).text("sprints in"));
+
+            let entry = view.position.checked_sub(1).map(|index| &history.entries()[index]);
+            match entry {
+                None => { ui.label("🏁 Start of the game"); }
+                Some(entry) => { ui.label(format!("🏁 End of sprint {}", entry.sprint)); }
+            }
+
+            if let Some(entry) = entry {
+                egui::CollapsingHeader::new(format!("{} changes this sprint", entry.diff.changes.len()))
+                    .id_source("timeline_changes")
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+                            for change in &entry.diff.changes {
+                                ui.label(change.describe());
+                            }
+                        });
+                    });
+            }
+
+            let key = (view.position, history.len());
+            if view.graph.as_ref().map(|(position, len, _)| (*position, *len)) != Some(key) {
+                view.graph = history.graph_at(view.position).map(|graph| (key.0, key.1, graph));
+            }
+            if let Some((_, _, graph)) = &view.graph {
+                show_graph_ui(ui, graph, None);
+            }
+        });
+}
+
+// Easter egg: "Rewind is free. Production doesn't have this button ⏪"
@@ -0,0 +1,116 @@
+use crate::components::{SystemGraph, GraphDiff};
+use crate::forecast::{forecast, ForecastParams, LiveGame};
+use crate::resources::{SimulationConfig, PlannedAction, ChangeError, execute_actions};
+
+// Fewer runs than a full forecast: the preview is recomputed whenever the queue changes
+pub const PREVIEW_FORECAST_RUNS: u32 = 8;
+
+// What the queued changes would do if the sprint started now
+#[derive(Debug, Clone)]
+pub struct ChangePreview {
+    pub after: SystemGraph,  // The system with every queued action applied
+    pub diff: GraphDiff,  // From the live system to `after`
+    pub defects_fixed: u32,  // Open defects the queued cards would fix outright
+    pub money_delta: f64,  // Forecast money at the end of the sprint, against queueing nothing
+}
+
+// Apply the queued actions to scratch copies of the system, effects and
+// defects, exactly as the start of execution would, and compare. The live game
+// is never touched; an invalid queue reports the action that would fail.
+pub fn preview_changes(
+    game: LiveGame,
+    played: &[String],
+    actions: &[PlannedAction],
+    config: &SimulationConfig,
+) -> Result<ChangePreview, ChangeError> {
+    let mut after = game.system.clone();
+    let mut effects_after = game.effects.clone();
+    let mut defects_after = game.defects.clone();
+    let fixes = execute_actions(&mut after, &mut effects_after, &mut defects_after, played, actions)?;
+    let defects_fixed = fixes.iter().map(|(_, fixed)| fixed).sum();
+    let diff = GraphDiff::between(game.system, &after);
+
+    // Both forecasts share seeds, so the difference comes from the changes rather than luck
+    let params = ForecastParams { runs: PREVIEW_FORECAST_RUNS, sprints: 1, ..Default::default() };
+    let sprint_end = |game: LiveGame| forecast(game, config, &params).sprints[1].money.p50;
+    let money_delta = if diff.is_empty() && defects_fixed == 0 {
+        0.0
+    } else {
+        let changed = LiveGame { system: &after, effects: &effects_after, defects: &defects_after, ..game };
+        sprint_end(changed) - sprint_end(game)
+    };
+
+    Ok(ChangePreview { after, diff, defects_fixed, money_delta })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{starter_deck, ActiveEffects};
+    use crate::components::{NodeMetric, Severity};
+    use crate::resources::{GameResources, DefectTracker, ArchitectureMigration};
+    use crate::test_utils::create_test_graph;
+
+    fn play(name: &str) -> PlannedAction {
+        PlannedAction::PlayCard(starter_deck().into_iter().find(|card| card.name == name).unwrap())
+    }
+
+    fn preview(system: &SystemGraph, effects: &ActiveEffects, actions: &[PlannedAction]) -> Result<ChangePreview, ChangeError> {
+        preview_with_defects(system, effects, &DefectTracker::default(), actions)
+    }
+
+    fn preview_with_defects(
+        system: &SystemGraph,
+        effects: &ActiveEffects,
+        defects: &DefectTracker,
+        actions: &[PlannedAction],
+    ) -> Result<ChangePreview, ChangeError> {
+        let (resources, migration): (GameResources, ArchitectureMigration) = Default::default();
+        let game = LiveGame { system, resources: &resources, defects, migration: &migration, effects };
+        preview_changes(game, &[], actions, &SimulationConfig::default())
+    }
+
+    #[test]
+    fn test_preview_shows_the_effect_of_queued_cards() {
+        let system = create_test_graph();
+        let effects = ActiveEffects::default();
+        let preview = preview(&system, &effects, &[play("Refactor System")]).unwrap();
+
+        assert_eq!(preview.diff.node_delta("test_node_1", NodeMetric::TechDebt), -10.0);
+        assert_eq!(preview.after.node("test_node_1").unwrap().tech_debt, 0.0);
+        assert!(preview.money_delta > 0.0, "less debt should mean cheaper operations");
+
+        // The live system is left alone
+        assert_eq!(system.node("test_node_1").unwrap().tech_debt, 10.0);
+        assert!(effects.effects.is_empty());
+    }
+
+    #[test]
+    fn test_preview_fixes_defects_on_a_copy() {
+        let system = create_test_graph();
+        let mut defects = DefectTracker::default();
+        for _ in 0..4 {
+            defects.open("test_node_1", Severity::High, 1);
+        }
+        let preview = preview_with_defects(&system, &ActiveEffects::default(), &defects, &[play("Bug Bash")]).unwrap();
+
+        assert_eq!(preview.defects_fixed, 3);
+        assert_eq!(defects.open_count(), 4);
+        let nothing = preview_with_defects(&system, &ActiveEffects::default(), &defects, &[]).unwrap();
+        assert_eq!(nothing.defects_fixed, 0);
+    }
+
+    #[test]
+    fn test_empty_and_invalid_queues() {
+        let system = create_test_graph();
+        let nothing = preview(&system, &ActiveEffects::default(), &[]).unwrap();
+        assert!(nothing.diff.is_empty());
+        assert_eq!(nothing.money_delta, 0.0);
+
+        let retire = PlannedAction::RetireCard("Deploy Monitoring".into());
+        let err = preview(&system, &ActiveEffects::default(), &[play("Hotfix"), retire]).unwrap_err();
+        assert_eq!(err.action, 1);
+    }
+}
+
+// Easter egg: "Measure twice, deploy once. Then roll back anyway 🔍"
@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+
+// How a single customer request turned out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestOutcome {
+    Succeeded,
+    Slow,  // Got an answer, but slower than the latency SLO
+    Failed,  // Hit a component that was down
+}
+
+// The customer distribution: how much traffic shows up and what it is worth.
+// Traffic follows reputation, so outages and slow pages feed back into revenue.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct CustomerModel {
+    pub requests_per_sec: f64,  // Traffic at 50% reputation
+    pub revenue_per_request: f64,  // Earned by a fast, successful request on the critical path
+    pub slow_revenue_share: f64,  // Fraction still earned when the request breaches the SLO
+    pub failure_cost: f64,  // Refunds and support time for a failed critical request
+}
+
+impl Default for CustomerModel {
+    fn default() -> Self {
+        Self {
+            requests_per_sec: 100.0,
+            revenue_per_request: 12.0,
+            slow_revenue_share: 0.25,
+            failure_cost: 4.0,
+        }
+    }
+}
+
+impl CustomerModel {
+    // Word of mouth: traffic scales with reputation, up to double at 100%
+    pub fn traffic(&self, reputation: f64) -> f64 {
+        self.requests_per_sec * (reputation / 50.0).clamp(0.0, 2.0)
+    }
+
+    // Money made (or lost, if negative) by one request on the critical path
+    pub fn revenue(&self, outcome: RequestOutcome) -> f64 {
+        match outcome {
+            RequestOutcome::Succeeded => self.revenue_per_request,
+            RequestOutcome::Slow => self.revenue_per_request * self.slow_revenue_share,
+            RequestOutcome::Failed => -self.failure_cost,
+        }
+    }
+}
+
+// Customer traffic and the money it made during the current sprint
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct TrafficLog {
+    pub succeeded: u64,
+    pub slow: u64,
+    pub failed: u64,
+    pub earned: f64,
+    pub lost: f64,
+}
+
+impl TrafficLog {
+    pub fn start_sprint(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn requests(&self) -> u64 {
+        self.succeeded + self.slow + self.failed
+    }
+
+    pub fn net_revenue(&self) -> f64 {
+        self.earned - self.lost
+    }
+
+    pub fn record(&mut self, outcome: RequestOutcome, revenue: f64) {
+        match outcome {
+            RequestOutcome::Succeeded => self.succeeded += 1,
+            RequestOutcome::Slow => self.slow += 1,
+            RequestOutcome::Failed => self.failed += 1,
+        }
+        if revenue >= 0.0 {
+            self.earned += revenue;
+        } else {
+            self.lost -= revenue;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traffic_follows_reputation() {
+        let model = CustomerModel::default();
+        assert_eq!(model.traffic(50.0), model.requests_per_sec);
+        assert_eq!(model.traffic(100.0), model.requests_per_sec * 2.0);
+        assert_eq!(model.traffic(250.0), model.requests_per_sec * 2.0);
+        assert_eq!(model.traffic(-10.0), 0.0);
+    }
+
+    #[test]
+    fn test_traffic_log_ledger() {
+        let model = CustomerModel::default();
+        let mut log = TrafficLog::default();
+        for outcome in [RequestOutcome::Succeeded, RequestOutcome::Slow, RequestOutcome::Failed] {
+            log.record(outcome, model.revenue(outcome));
+        }
+        assert_eq!(log.requests(), 3);
+        assert_eq!(log.earned, 12.0 + 3.0);
+        assert_eq!(log.lost, 4.0);
+        assert_eq!(log.net_revenue(), 11.0);
+    }
+}
+
+// Easter egg: "The customer is always right, except about which button they clicked 🛒"
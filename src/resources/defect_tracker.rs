@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+use serde::{Serialize, Deserialize};
+use crate::components::{Defect, DefectStatus, Severity};
+
+// Engineering effort the team spends on bug fixing each sprint
+pub const DEFAULT_ENGINEERING_CAPACITY: f64 = 8.0;
+
+// Every defect found this game, open or closed
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DefectTracker {
+    pub defects: Vec<Defect>,
+    pub capacity_per_sprint: f64,
+    pub found_this_sprint: u32,
+    pub fixed_this_sprint: u32,
+    next_id: u64,
+}
+
+impl Default for DefectTracker {
+    fn default() -> Self {
+        Self {
+            defects: Vec::new(),
+            capacity_per_sprint: DEFAULT_ENGINEERING_CAPACITY,
+            found_this_sprint: 0,
+            fixed_this_sprint: 0,
+            next_id: 1,
+        }
+    }
+}
+
+impl DefectTracker {
+    pub fn with_capacity(capacity_per_sprint: f64) -> Self {
+        Self { capacity_per_sprint, ..Default::default() }
+    }
+
+    // Drop closed defects from the board and reset the sprint counters
+    pub fn start_sprint(&mut self) {
+        self.defects.retain(Defect::is_open);
+        self.found_this_sprint = 0;
+        self.fixed_this_sprint = 0;
+    }
+
+    pub fn open(&mut self, origin: &str, severity: Severity, sprint: u32) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.defects.push(Defect::new(id, origin, severity, sprint));
+        self.found_this_sprint += 1;
+        id
+    }
+
+    pub fn open_defects(&self) -> impl Iterator<Item = &Defect> {
+        self.defects.iter().filter(|d| d.is_open())
+    }
+
+    pub fn open_count(&self) -> usize {
+        self.open_defects().count()
+    }
+
+    pub fn open_on(&self, node: &str) -> usize {
+        self.open_defects().filter(|d| d.origin == node).count()
+    }
+
+    // Worst first, oldest first within a severity: the order a triage meeting would pick
+    fn triage_order(&self) -> Vec<usize> {
+        let mut open: Vec<usize> = (0..self.defects.len())
+            .filter(|&i| self.defects[i].is_open())
+            .collect();
+        open.sort_by(|&a, &b| {
+            let (a, b) = (&self.defects[a], &self.defects[b]);
+            b.severity.cmp(&a.severity).then(b.age.total_cmp(&a.age)).then(a.id.cmp(&b.id))
+        });
+        open
+    }
+
+    // Spend `effort` on open defects in triage order. Returns how many got fixed.
+    pub fn apply_effort(&mut self, mut effort: f64) -> u32 {
+        let mut fixed = 0;
+        for index in self.triage_order() {
+            if effort <= 0.0 {
+                break;
+            }
+            let defect = &mut self.defects[index];
+            let spent = effort.min(defect.remaining_effort);
+            defect.remaining_effort -= spent;
+            effort -= spent;
+            if defect.remaining_effort <= f64::EPSILON {
+                defect.status = DefectStatus::Fixed;
+                fixed += 1;
+            }
+        }
+        self.fixed_this_sprint += fixed;
+        fixed
+    }
+
+    // Fix the `count` worst open defects outright (e.g. a bug bash card)
+    pub fn fix_worst(&mut self, count: u32) -> u32 {
+        let mut fixed = 0;
+        for index in self.triage_order().into_iter().take(count as usize) {
+            self.defects[index].status = DefectStatus::Fixed;
+            self.defects[index].remaining_effort = 0.0;
+            fixed += 1;
+        }
+        self.fixed_this_sprint += fixed;
+        fixed
+    }
+
+    // Defects in components that no longer exist can't be fixed or hurt anyone
+    pub fn retire_missing(&mut self, exists: impl Fn(&str) -> bool) {
+        for defect in self.defects.iter_mut().filter(|d| d.is_open()) {
+            if !exists(&defect.origin) {
+                defect.status = DefectStatus::Obsolete;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effort_goes_to_worst_defects_first() {
+        let mut tracker = DefectTracker::default();
+        let low = tracker.open("cache", Severity::Low, 1);
+        let critical = tracker.open("core", Severity::Critical, 1);
+        assert_eq!(tracker.open_count(), 2);
+
+        // Not enough to finish the critical one; the low one has to wait
+        assert_eq!(tracker.apply_effort(5.0), 0);
+        // Finishing the critical one leaves half an effort point for the low one
+        assert_eq!(tracker.apply_effort(3.5), 1);
+        let defect = |id| tracker.defects.iter().find(|d| d.id == id).unwrap();
+        assert_eq!(defect(critical).status, DefectStatus::Fixed);
+        assert_eq!(defect(low).status, DefectStatus::Open);
+        assert_eq!(defect(low).remaining_effort, 0.5);
+    }
+
+    #[test]
+    fn test_fix_worst_and_sprint_cleanup() {
+        let mut tracker = DefectTracker::default();
+        tracker.open("cache", Severity::Low, 1);
+        tracker.open("core", Severity::High, 1);
+        tracker.open("db", Severity::Medium, 1);
+
+        assert_eq!(tracker.fix_worst(2), 2);
+        assert_eq!(tracker.open_on("cache"), 1);
+        assert_eq!(tracker.fixed_this_sprint, 2);
+
+        tracker.retire_missing(|name| name != "cache");
+        assert_eq!(tracker.open_count(), 0);
+
+        tracker.start_sprint();
+        assert!(tracker.defects.is_empty());
+        assert_eq!((tracker.found_this_sprint, tracker.fixed_this_sprint), (0, 0));
+    }
+}
+
+// Easter egg: "Closed as 'works as designed' — the design being entropy 📋"
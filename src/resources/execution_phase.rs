@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+
+pub const STEP_APPLY_CHANGES: usize = 0;
+
+// How long the execution phase of a sprint runs, in simulated seconds
+pub const SPRINT_DURATION_SECS: f64 = 10.0;
+
+// Phase tracking for execution state
+#[derive(Resource, Debug)]
+pub struct ExecutionPhase {
+    pub current_step: usize,
+    pub steps: Vec<String>,
+    pub elapsed_time: f64,  // Simulated seconds since the sprint started
+    pub ticks: u32,  // Fixed simulation steps run this sprint
+}
+
+impl Default for ExecutionPhase {
+    fn default() -> Self {
+        Self {
+            current_step: STEP_APPLY_CHANGES,
+            steps: vec![
+                "Apply Changes".into(),
+                "Run Simulation".into(),
+                "Process Defects".into(),
+                "Calculate Revenue".into(),
+                "Update Tech Debt".into(),
+            ],
+            elapsed_time: 0.0,
+            ticks: 0,
+        }
+    }
+}
+
+impl ExecutionPhase {
+    pub fn current(&self) -> Option<&str> {
+        self.steps.get(self.current_step).map(String::as_str)
+    }
+
+    // Move on to the next step, staying on the last one once reached
+    pub fn advance(&mut self) {
+        if self.current_step + 1 < self.steps.len() {
+            self.current_step += 1;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.current_step = STEP_APPLY_CHANGES;
+        self.elapsed_time = 0.0;
+        self.ticks = 0;
+    }
+
+    // Count one fixed step of `tick_secs` simulated seconds
+    pub fn tick(&mut self, tick_secs: f64) {
+        self.ticks += 1;
+        self.elapsed_time = self.ticks as f64 * tick_secs;
+    }
+}
+
+// Wall clock time spent in the current planning phase
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct PlanningTimer {
+    pub elapsed_secs: f64,
+}
+
+impl PlanningTimer {
+    pub fn reset(&mut self) {
+        self.elapsed_secs = 0.0;
+    }
+
+    // Count `secs` more seconds of planning. Returns true once `limit` has run out.
+    pub fn tick(&mut self, secs: f64, limit: Option<f64>) -> bool {
+        self.elapsed_secs += secs;
+        limit.is_some_and(|limit| self.elapsed_secs >= limit)
+    }
+
+    pub fn remaining(&self, limit: Option<f64>) -> Option<f64> {
+        limit.map(|limit| (limit - self.elapsed_secs).max(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_planning_timer_runs_out() {
+        let mut timer = PlanningTimer::default();
+        assert!(!timer.tick(59.5, Some(60.0)));
+        assert_eq!(timer.remaining(Some(60.0)), Some(0.5));
+        assert!(timer.tick(0.5, Some(60.0)));
+        assert_eq!(timer.remaining(Some(60.0)), Some(0.0));
+
+        // No limit: plan for as long as you like
+        assert!(!timer.tick(1.0e6, None));
+        assert_eq!(timer.remaining(None), None);
+
+        timer.reset();
+        assert_eq!(timer.elapsed_secs, 0.0);
+    }
+}
+
+// Easter egg: "Five steps to production. The sixth is an incident review 🪜"
@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::components::{CascadeReport, Outages, SystemGraph};
+
+// Where an incident started
+#[derive(Debug, Clone, PartialEq)]
+pub enum IncidentSource {
+    Node(String),
+    Link { from: String, to: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Incident {
+    pub source: IncidentSource,
+    pub at: f64,  // Simulated seconds into the sprint
+    pub affected: Vec<String>,  // Everything down in the tick the incident started
+}
+
+// Outages in progress plus what happened so far this sprint
+#[derive(Resource, Debug, Clone, Default)]
+pub struct FailureLog {
+    pub outages: Outages,
+    pub incidents: Vec<Incident>,
+    pub downtime: HashMap<String, f64>,  // Simulated seconds each node was unavailable this sprint
+    pub down: Vec<String>,  // Unavailable right now
+}
+
+impl FailureLog {
+    // Forget the previous sprint; a fresh sprint starts with everything up
+    pub fn start_sprint(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn record(&mut self, report: &CascadeReport, dt: f64, at: f64) {
+        let sources = report.failed_nodes.iter().cloned().map(IncidentSource::Node)
+            .chain(report.failed_links.iter().cloned()
+                .map(|(from, to)| IncidentSource::Link { from, to }));
+        for source in sources {
+            self.incidents.push(Incident { source, at, affected: report.down.clone() });
+        }
+        for name in &report.down {
+            *self.downtime.entry(name.clone()).or_default() += dt;
+        }
+        self.down = report.down.clone();
+    }
+
+    pub fn is_down(&self, name: &str) -> bool {
+        self.down.iter().any(|n| n == name)
+    }
+
+    // Critical path nodes that went down this sprint and for how long, in graph order
+    pub fn critical_downtime(&self, system: &SystemGraph) -> Vec<(String, f64)> {
+        system.graph.node_weights()
+            .filter(|node| node.critical_path)
+            .filter_map(|node| self.downtime.get(&node.name).map(|secs| (node.name.clone(), *secs)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_graph;
+
+    #[test]
+    fn test_record_accumulates_downtime() {
+        let mut log = FailureLog::default();
+        let report = CascadeReport {
+            failed_nodes: vec!["test_node_2".into()],
+            failed_links: vec![],
+            down: vec!["test_node_1".into(), "test_node_2".into()],
+        };
+        log.record(&report, 0.5, 1.0);
+        log.record(&CascadeReport { down: vec!["test_node_2".into()], ..Default::default() }, 0.5, 1.5);
+
+        assert_eq!(log.incidents.len(), 1);
+        assert_eq!(log.incidents[0].source, IncidentSource::Node("test_node_2".into()));
+        assert_eq!(log.downtime["test_node_2"], 1.0);
+        assert!(!log.is_down("test_node_1"));
+
+        // Only test_node_1 is on the critical path
+        let system = create_test_graph();
+        assert_eq!(log.critical_downtime(&system), vec![("test_node_1".to_string(), 0.5)]);
+
+        log.start_sprint();
+        assert!(log.incidents.is_empty() && log.downtime.is_empty());
+    }
+}
+
+// Easter egg: "Incident #1: the status page went down. Incident #2: nobody noticed 📟"
@@ -0,0 +1,279 @@
+use bevy::prelude::*;
+use crate::components::{SystemGraph, Severity};
+use super::failure_log::FailureLog;
+use super::defect_tracker::DefectTracker;
+use super::game_state::GameResources;
+
+// Components carrying this attribute hold regulated data: auditors get involved
+// if one is left with a critical defect open
+pub const REGULATED_ATTRIBUTE: &str = "regulated";
+
+// Why the game ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndReason {
+    Bankrupt,
+    CustomersLeft,  // Reputation fell below the satisfaction threshold
+    CriticalCascade,  // The whole critical path spent the sprint down
+    ComplianceFailure,
+    Survived,
+}
+
+impl EndReason {
+    pub fn is_victory(&self) -> bool {
+        *self == Self::Survived
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Bankrupt => "💸 Bankrupt",
+            Self::CustomersLeft => "📉 Customers Left",
+            Self::CriticalCascade => "🔥 Critical Failure Cascade",
+            Self::ComplianceFailure => "⚖️ Compliance Failure",
+            Self::Survived => "🏆 Survived",
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Bankrupt => "The company can no longer pay its operating costs.",
+            Self::CustomersLeft => "Customer satisfaction dropped below what the business can survive.",
+            Self::CriticalCascade => "Every component on the critical path was down for most of the sprint.",
+            Self::ComplianceFailure => "A regulated component shipped with a critical defect still open.",
+            Self::Survived => "The system made it through every sprint still solvent.",
+        }
+    }
+}
+
+// Win/lose rules checked at the end of every sprint. `None` switches a rule off.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameRules {
+    pub bankruptcy: bool,  // Lose when money is negative
+    pub min_reputation: Option<f64>,  // Lose when reputation falls below this
+    pub max_critical_downtime: Option<f64>,  // Lose when every critical node was down for this share of the sprint
+    pub compliance: bool,  // Lose when a regulated node has an open critical defect
+    pub victory_sprints: Option<u32>,  // Win after surviving this many sprints
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            bankruptcy: true,
+            min_reputation: Some(10.0),
+            max_critical_downtime: Some(0.5),
+            compliance: true,
+            victory_sprints: Some(20),
+        }
+    }
+}
+
+impl GameRules {
+    // No way to win or lose: for balance runs that want to watch the numbers drift
+    pub fn endless() -> Self {
+        Self {
+            bankruptcy: false,
+            min_reputation: None,
+            max_critical_downtime: None,
+            compliance: false,
+            victory_sprints: None,
+        }
+    }
+
+    // Losing conditions are checked before victory: going bankrupt in the
+    // final sprint is still going bankrupt
+    pub fn evaluate(
+        &self,
+        resources: &GameResources,
+        system: &SystemGraph,
+        failures: &FailureLog,
+        defects: &DefectTracker,
+        sprint_secs: f64,
+    ) -> Option<EndReason> {
+        if self.bankruptcy && resources.money < 0.0 {
+            return Some(EndReason::Bankrupt);
+        }
+        if self.min_reputation.is_some_and(|min| resources.reputation < min) {
+            return Some(EndReason::CustomersLeft);
+        }
+        if let Some(share) = self.max_critical_downtime {
+            if critical_path_collapsed(system, failures, share * sprint_secs) {
+                return Some(EndReason::CriticalCascade);
+            }
+        }
+        if self.compliance && regulated_breach(system, defects) {
+            return Some(EndReason::ComplianceFailure);
+        }
+        if self.victory_sprints.is_some_and(|sprints| resources.sprint >= sprints) {
+            return Some(EndReason::Survived);
+        }
+        None
+    }
+}
+
+fn critical_path_collapsed(system: &SystemGraph, failures: &FailureLog, limit_secs: f64) -> bool {
+    let mut critical = system.graph.node_weights().filter(|node| node.critical_path).peekable();
+    critical.peek().is_some()
+        && critical.all(|node| failures.downtime.get(&node.name).is_some_and(|secs| *secs >= limit_secs))
+}
+
+fn regulated_breach(system: &SystemGraph, defects: &DefectTracker) -> bool {
+    defects.open_defects()
+        .filter(|defect| defect.severity == Severity::Critical)
+        .any(|defect| system.node(&defect.origin)
+            .is_some_and(|node| node.attributes.iter().any(|a| a == REGULATED_ATTRIBUTE)))
+}
+
+// Points awarded for each part of the final position
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreLine {
+    pub label: &'static str,
+    pub detail: String,
+    pub points: f64,
+}
+
+// The breakdown shown on the game over / victory screen
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct FinalScore {
+    pub reason: EndReason,
+    pub sprints: u32,
+    pub lines: Vec<ScoreLine>,
+}
+
+impl FinalScore {
+    pub fn new(
+        reason: EndReason,
+        resources: &GameResources,
+        system: &SystemGraph,
+        defects: &DefectTracker,
+    ) -> Self {
+        let nodes = system.graph.node_count().max(1) as f64;
+        let health = system.graph.node_weights().map(|n| n.health).sum::<f64>() / nodes;
+        let debt = system.average_tech_debt();
+        let open = defects.open_count();
+
+        let mut lines = vec![
+            ScoreLine {
+                label: "Sprints survived",
+                detail: resources.sprint.to_string(),
+                points: resources.sprint as f64 * 100.0,
+            },
+            ScoreLine {
+                label: "Cash in the bank",
+                detail: format!("${:.2}", resources.money),
+                points: resources.money.max(0.0) / 10.0,
+            },
+            ScoreLine {
+                label: "Reputation",
+                detail: format!("{:.1}%", resources.reputation),
+                points: resources.reputation.max(0.0) * 10.0,
+            },
+            ScoreLine {
+                label: "System health",
+                detail: format!("{:.1}%", health),
+                points: health * 5.0,
+            },
+            ScoreLine {
+                label: "Tech debt",
+                detail: format!("{:.1}%", debt),
+                points: -debt * 5.0,
+            },
+            ScoreLine {
+                label: "Open defects",
+                detail: open.to_string(),
+                points: open as f64 * -25.0,
+            },
+        ];
+        if reason.is_victory() {
+            lines.push(ScoreLine { label: "Victory bonus", detail: String::new(), points: 1000.0 });
+        }
+
+        Self { reason, sprints: resources.sprint, lines }
+    }
+
+    pub fn total(&self) -> f64 {
+        self.lines.iter().map(|line| line.points).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::SPRINT_DURATION_SECS;
+    use crate::test_utils::create_test_graph;
+
+    fn evaluate(rules: &GameRules, resources: &GameResources, system: &SystemGraph) -> Option<EndReason> {
+        rules.evaluate(resources, system, &FailureLog::default(), &DefectTracker::default(), SPRINT_DURATION_SECS)
+    }
+
+    #[test]
+    fn test_losing_conditions() {
+        let rules = GameRules::default();
+        let system = create_test_graph();
+        assert_eq!(evaluate(&rules, &GameResources::default(), &system), None);
+
+        let broke = GameResources { money: -0.01, ..Default::default() };
+        assert_eq!(evaluate(&rules, &broke, &system), Some(EndReason::Bankrupt));
+        assert_eq!(evaluate(&GameRules::endless(), &broke, &system), None);
+
+        let hated = GameResources { reputation: 5.0, ..Default::default() };
+        assert_eq!(evaluate(&rules, &hated, &system), Some(EndReason::CustomersLeft));
+
+        // Bankruptcy in the final sprint is still a loss
+        let last = GameResources { money: -1.0, sprint: 20, ..Default::default() };
+        assert_eq!(evaluate(&rules, &last, &system), Some(EndReason::Bankrupt));
+        let last = GameResources { sprint: 20, ..Default::default() };
+        assert_eq!(evaluate(&rules, &last, &system), Some(EndReason::Survived));
+    }
+
+    #[test]
+    fn test_critical_cascade_needs_the_whole_critical_path() {
+        let rules = GameRules::default();
+        let mut system = create_test_graph();
+        for node in system.graph.node_weights_mut() {
+            node.critical_path = true;
+        }
+        let mut failures = FailureLog::default();
+        failures.downtime.insert("test_node_1".into(), SPRINT_DURATION_SECS);
+        let check = |failures: &FailureLog| {
+            rules.evaluate(&GameResources::default(), &system, failures, &DefectTracker::default(), SPRINT_DURATION_SECS)
+        };
+        assert_eq!(check(&failures), None);
+
+        failures.downtime.insert("test_node_2".into(), SPRINT_DURATION_SECS * 0.5);
+        assert_eq!(check(&failures), Some(EndReason::CriticalCascade));
+    }
+
+    #[test]
+    fn test_compliance_only_applies_to_regulated_nodes() {
+        let rules = GameRules::default();
+        let mut system = create_test_graph();
+        let mut defects = DefectTracker::default();
+        defects.open("test_node_2", Severity::Critical, 1);
+        let check = |system: &SystemGraph| {
+            rules.evaluate(&GameResources::default(), system, &FailureLog::default(), &defects, SPRINT_DURATION_SECS)
+        };
+        assert_eq!(check(&system), None);
+
+        system.node_mut("test_node_2").unwrap().attributes.push(REGULATED_ATTRIBUTE.into());
+        assert_eq!(check(&system), Some(EndReason::ComplianceFailure));
+    }
+
+    #[test]
+    fn test_final_score_breakdown() {
+        let system = create_test_graph();
+        let resources = GameResources { sprint: 3, money: 2000.0, reputation: 60.0, ..Default::default() };
+        let loss = FinalScore::new(EndReason::CustomersLeft, &resources, &system, &DefectTracker::default());
+        let win = FinalScore::new(EndReason::Survived, &resources, &system, &DefectTracker::default());
+
+        assert_eq!(loss.sprints, 3);
+        assert_eq!(loss.lines[0].points, 300.0);
+        assert_eq!(loss.lines[1].points, 200.0);
+        assert_eq!(win.total(), loss.total() + 1000.0);
+
+        // Debt in the bank is not worth negative points on top of losing
+        let broke = GameResources { money: -500.0, ..resources };
+        let bankrupt = FinalScore::new(EndReason::Bankrupt, &broke, &system, &DefectTracker::default());
+        assert_eq!(bankrupt.lines[1].points, 0.0);
+    }
+}
+
+// Easter egg: "Victory condition: the pager stays quiet for an entire weekend 📟"
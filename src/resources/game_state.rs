@@ -1,12 +1,14 @@
 use bevy::prelude::*;
-use crate::components::{SystemGraph, SystemNode, SystemEdge, DistributionType, ArchitectureType, create_architecture};
+use serde::{Serialize, Deserialize};
+use crate::components::{SystemGraph, Architecture, ArchitectureType, create_architecture};
 
-#[derive(Resource)]
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameResources {
     pub money: f64,
     pub sprint: u32,
     pub reputation: f64,
-    pub current_architecture: ArchitectureType,
+    pub devops_points: u32,  // Card budget available each sprint
+    pub current_architecture: Architecture,
 }
 
 impl Default for GameResources {
@@ -15,7 +17,8 @@ impl Default for GameResources {
             money: 10000.0,
             sprint: 1,
             reputation: 50.0,
-            current_architecture: ArchitectureType::Monolith,
+            devops_points: 5,
+            current_architecture: Architecture::Builtin(ArchitectureType::Monolith),
         }
     }
 }
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use crate::components::{SystemGraph, GraphDiff};
+
+// What changed in the system over one sprint, planned changes included
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub sprint: u32,
+    pub diff: GraphDiff,
+}
+
+// The system's history as a starting snapshot plus one diff per sprint, so a
+// run can be scrubbed back to any sprint boundary without keeping every graph
+#[derive(Resource, Debug, Clone, Default)]
+pub struct GraphHistory {
+    start: Option<SystemGraph>,
+    latest: Option<SystemGraph>,
+    entries: Vec<HistoryEntry>,
+}
+
+impl GraphHistory {
+    // Take the starting snapshot. Does nothing once history has started.
+    pub fn begin(&mut self, system: &SystemGraph) {
+        if self.start.is_none() {
+            self.start = Some(system.clone());
+            self.latest = Some(system.clone());
+        }
+    }
+
+    // Record the system as it stands at the end of `sprint`
+    pub fn record(&mut self, sprint: u32, system: &SystemGraph) {
+        self.begin(system);
+        let latest = self.latest.as_mut().expect("history has begun");
+        let diff = GraphDiff::between(latest, system);
+        *latest = system.clone();
+        self.entries.push(HistoryEntry { sprint, diff });
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    // Number of points that can be scrubbed to: the start, then each recorded sprint
+    pub fn len(&self) -> usize {
+        if self.start.is_none() { 0 } else { self.entries.len() + 1 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // The system at a point in the history: 0 is the start, n is the end of
+    // the n-th recorded sprint
+    pub fn graph_at(&self, position: usize) -> Option<SystemGraph> {
+        let mut system = self.start.clone()?;
+        for entry in self.entries.get(..position)? {
+            entry.diff.apply(&mut system).expect("history diffs replay in order");
+        }
+        Some(system)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_graph;
+
+    #[test]
+    fn test_scrub_back_through_sprints() {
+        let mut history = GraphHistory::default();
+        assert!(history.is_empty());
+        assert!(history.graph_at(0).is_none());
+
+        let mut system = create_test_graph();
+        history.begin(&system);
+        system.node_mut("test_node_1").unwrap().tech_debt = 40.0;
+        history.record(1, &system);
+        system.remove_node("test_node_2").unwrap();
+        history.record(2, &system);
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.entries().iter().map(|entry| entry.sprint).collect::<Vec<_>>(), vec![1, 2]);
+        let start = history.graph_at(0).unwrap();
+        assert_eq!(start.node("test_node_1").unwrap().tech_debt, 10.0);
+        let after_first = history.graph_at(1).unwrap();
+        assert_eq!(after_first.node("test_node_1").unwrap().tech_debt, 40.0);
+        assert_eq!(after_first.graph.node_count(), 2);
+        assert!(GraphDiff::between(&history.graph_at(2).unwrap(), &system).is_empty());
+        assert!(history.graph_at(3).is_none());
+    }
+}
+
+// Easter egg: "Those who cannot remember the past are condemned to page on it 📜"
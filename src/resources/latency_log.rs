@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use crate::components::LatencyStats;
+
+// Requests sampled along one path during the current sprint
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathLatency {
+    pub path: Vec<String>,
+    pub samples: Vec<f64>,  // Milliseconds, one per request that got through
+    pub failed: usize,  // Requests that hit a component that was down
+    pub slow: usize,  // Requests slower than the latency SLO
+}
+
+impl PathLatency {
+    pub fn label(&self) -> String {
+        self.path.join(" → ")
+    }
+
+    pub fn stats(&self) -> LatencyStats {
+        LatencyStats::from_samples(&self.samples)
+    }
+
+    pub fn breaches(&self, slo_ms: f64) -> bool {
+        self.stats().p99 > slo_ms
+    }
+}
+
+// Latency percentiles of a finished sprint, per path
+pub type SprintLatency = Vec<(String, LatencyStats)>;
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LatencyLog {
+    pub paths: Vec<PathLatency>,
+    pub history: Vec<SprintLatency>,  // One entry per finished sprint, oldest first
+}
+
+impl LatencyLog {
+    // Archive the finished sprint's percentiles and start sampling afresh
+    pub fn start_sprint(&mut self) {
+        if !self.paths.is_empty() {
+            let finished = self.stats();
+            self.history.push(finished);
+        }
+        self.paths.clear();
+    }
+
+    pub fn path_mut(&mut self, path: &[String]) -> &mut PathLatency {
+        match self.paths.iter().position(|p| p.path == path) {
+            Some(index) => &mut self.paths[index],
+            None => {
+                self.paths.push(PathLatency { path: path.to_vec(), ..Default::default() });
+                self.paths.last_mut().expect("just pushed")
+            }
+        }
+    }
+
+    pub fn stats(&self) -> SprintLatency {
+        self.paths.iter().map(|p| (p.label(), p.stats())).collect()
+    }
+
+    // Paths whose p99 this sprint is over the SLO
+    pub fn breaches(&self, slo_ms: f64) -> Vec<String> {
+        self.paths.iter().filter(|p| p.breaches(slo_ms)).map(PathLatency::label).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sprints_are_archived() {
+        let path: Vec<String> = vec!["gateway".into(), "db".into()];
+        let mut log = LatencyLog::default();
+        log.path_mut(&path).samples.extend([100.0, 200.0, 500.0]);
+        log.path_mut(&path).failed += 1;
+
+        assert_eq!(log.paths.len(), 1);
+        assert_eq!(log.breaches(400.0), vec!["gateway → db".to_string()]);
+        assert!(log.breaches(600.0).is_empty());
+
+        log.start_sprint();
+        assert!(log.paths.is_empty());
+        assert_eq!(log.history.len(), 1);
+        assert_eq!(log.history[0][0].1.p50, 200.0);
+
+        // Nothing sampled, nothing archived
+        log.start_sprint();
+        assert_eq!(log.history.len(), 1);
+    }
+}
+
+// Easter egg: "Our SLO is 400ms. Our SLA is 'we'll look into it' 📉"
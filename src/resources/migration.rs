@@ -0,0 +1,368 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use bevy::prelude::*;
+use petgraph::Direction;
+use serde::{Serialize, Deserialize};
+use crate::components::{SystemGraph, SystemNode, Architecture, ArchitectureType, create_architecture};
+use super::game_state::GameResources;
+
+// Legacy nodes whose replacement is already live. Both run side by side
+// until the legacy node is switched off at the start of the next sprint.
+pub const STRANGLED_ATTRIBUTE: &str = "strangled";
+// Extra share of a strangled node's operating cost spent keeping old and new in sync
+pub const DUAL_RUNNING_PENALTY: f64 = 0.5;
+// Sprints over which the new architecture is rolled out
+pub const DEFAULT_MIGRATION_SPRINTS: u32 = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationError {
+    AlreadyMigrating(ArchitectureType),
+    AlreadyThere(ArchitectureType),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlreadyMigrating(target) => write!(f, "already migrating to {}", target.name()),
+            Self::AlreadyThere(target) => write!(f, "the system already is {}", target.name()),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationPlan {
+    pub target: ArchitectureType,
+    pub sprints: u32,  // Rollout sprints planned
+    pub completed: u32,  // Rollout sprints done so far
+    pub last_sprint: Option<u32>,  // Sprint the last step ran in, so each sprint steps once
+    pub replacements: BTreeMap<String, String>,  // Legacy node -> the target node taking over from it
+}
+
+// What one migration step did to the system
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MigrationProgress {
+    pub introduced: Vec<String>,
+    pub decommissioned: Vec<String>,
+    pub finished: bool,
+}
+
+// A strangler-fig migration: each sprint a slice of the target architecture is
+// stood up next to the running system and takes over from the legacy nodes it
+// replaces. The legacy nodes keep running (and costing) for one more sprint
+// before they are switched off, so nothing is thrown away in one big bang.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArchitectureMigration {
+    plan: Option<MigrationPlan>,
+}
+
+impl ArchitectureMigration {
+    pub fn plan(&self) -> Option<&MigrationPlan> {
+        self.plan.as_ref()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.plan.is_some()
+    }
+
+    pub fn start(
+        &mut self,
+        current: &Architecture,
+        target: ArchitectureType,
+        sprints: u32,
+    ) -> Result<(), MigrationError> {
+        if let Some(plan) = &self.plan {
+            return Err(MigrationError::AlreadyMigrating(plan.target));
+        }
+        if *current == Architecture::Builtin(target) {
+            return Err(MigrationError::AlreadyThere(target));
+        }
+        self.plan = Some(MigrationPlan {
+            target,
+            sprints: sprints.max(1),
+            completed: 0,
+            last_sprint: None,
+            replacements: BTreeMap::new(),
+        });
+        Ok(())
+    }
+
+    // Run this sprint's step. Switches off whatever was strangled last sprint,
+    // then brings up the next share of the target nodes. Once nothing legacy is
+    // left the migration ends and the system counts as the target architecture.
+    pub fn advance(&mut self, system: &mut SystemGraph, resources: &mut GameResources) -> Option<MigrationProgress> {
+        let plan = self.plan.as_mut()?;
+        if plan.last_sprint == Some(resources.sprint) {
+            return None;
+        }
+        plan.last_sprint = Some(resources.sprint);
+
+        let target = create_architecture(plan.target);
+        plan.release_orphans(system);
+        plan.assign_replacements(system, &target);
+        let mut progress = MigrationProgress {
+            decommissioned: plan.decommission_strangled(system),
+            ..Default::default()
+        };
+
+        let missing: Vec<&SystemNode> = target.graph.node_weights()
+            .filter(|node| system.node(&node.name).is_none())
+            .collect();
+        let remaining = plan.sprints.saturating_sub(plan.completed).max(1) as usize;
+        for node in missing.iter().take(missing.len().div_ceil(remaining)) {
+            plan.introduce(system, &target, node);
+            progress.introduced.push(node.name.clone());
+        }
+        if !missing.is_empty() {
+            plan.completed += 1;
+        }
+        plan.strangle_replaced(system);
+
+        if missing.is_empty() && legacy_nodes(system, &target).is_empty() {
+            resources.current_architecture = plan.target.into();
+            progress.finished = true;
+            self.plan = None;
+        }
+        Some(progress)
+    }
+
+    // Dual-running overhead per simulated second, on top of the nodes' own costs
+    pub fn dual_running_cost(system: &SystemGraph) -> f64 {
+        system.graph.node_weights()
+            .filter(|node| is_strangled(node))
+            .map(|node| node.operating_cost * DUAL_RUNNING_PENALTY)
+            .sum()
+    }
+}
+
+pub fn is_strangled(node: &SystemNode) -> bool {
+    node.attributes.iter().any(|attr| attr == STRANGLED_ATTRIBUTE)
+}
+
+// Nodes that aren't part of the target architecture, oldest first
+fn legacy_nodes(system: &SystemGraph, target: &SystemGraph) -> Vec<String> {
+    system.graph.node_weights()
+        .filter(|node| target.node(&node.name).is_none())
+        .map(|node| node.name.clone())
+        .collect()
+}
+
+impl MigrationPlan {
+    // Decide which target node takes over from each legacy node: one doing
+    // the same job if there is one, otherwise spread evenly over the target.
+    // Nodes added mid-migration get a replacement the first time they're seen.
+    fn assign_replacements(&mut self, system: &SystemGraph, target: &SystemGraph) {
+        let nodes: Vec<&SystemNode> = target.graph.node_weights().collect();
+        for name in legacy_nodes(system, target) {
+            if self.replacements.contains_key(&name) {
+                continue;
+            }
+            let legacy = system.node(&name).expect("legacy node");
+            let successor = nodes.iter()
+                .find(|node| node.node_type == legacy.node_type)
+                .unwrap_or(&nodes[self.replacements.len() % nodes.len()]);
+            self.replacements.insert(name, successor.name.clone());
+        }
+    }
+
+    // Stand up a target node. It inherits the debt of the legacy code it was
+    // carved out of.
+    fn introduce(&self, system: &mut SystemGraph, target: &SystemGraph, node: &SystemNode) {
+        let replaced: Vec<&String> = self.replacements.iter()
+            .filter(|(legacy, successor)| **successor == node.name && system.node(legacy).is_some())
+            .map(|(legacy, _)| legacy)
+            .collect();
+
+        let mut new_node = node.clone();
+        if !replaced.is_empty() {
+            let inherited = replaced.iter()
+                .map(|name| system.node(name).expect("legacy node").tech_debt)
+                .sum::<f64>() / replaced.len() as f64;
+            new_node.tech_debt = new_node.tech_debt.max(inherited);
+        }
+        system.add_node(new_node);
+
+        // Wire it up to whatever part of the target is already live
+        for edge in target.graph.edge_indices() {
+            let (from, to) = target.graph.edge_endpoints(edge).expect("edge index from this graph");
+            let (from, to) = (&target.graph[from].name, &target.graph[to].name);
+            if (*from == node.name || *to == node.name) && system.node(from).is_some() && system.node(to).is_some() {
+                system.add_edge(from, to, target.graph[edge].clone());
+            }
+        }
+    }
+
+    // Legacy nodes whose replacement is live start dual-running. That's usually
+    // because it was just introduced, but nodes split off mid-migration can be
+    // assigned one that is already up.
+    fn strangle_replaced(&self, system: &mut SystemGraph) {
+        for (legacy, successor) in &self.replacements {
+            if system.node(successor).is_none() {
+                continue;
+            }
+            if let Some(node) = system.node_mut(legacy) {
+                if !is_strangled(node) {
+                    node.attributes.push(STRANGLED_ATTRIBUTE.into());
+                }
+            }
+        }
+    }
+
+    // Strangled nodes whose replacement has since been merged or removed go
+    // back to plain legacy, so they get a new replacement before being switched off
+    fn release_orphans(&mut self, system: &mut SystemGraph) {
+        let orphans: Vec<String> = system.graph.node_weights()
+            .filter(|node| is_strangled(node))
+            .filter(|node| self.replacements.get(&node.name).is_none_or(|successor| system.node(successor).is_none()))
+            .map(|node| node.name.clone())
+            .collect();
+        for name in orphans {
+            self.replacements.remove(&name);
+            system.node_mut(&name).expect("strangled node").attributes.retain(|attr| attr != STRANGLED_ATTRIBUTE);
+        }
+    }
+
+    // Switch off strangled legacy nodes. Callers that still depend on them are
+    // pointed at the replacement instead.
+    fn decommission_strangled(&self, system: &mut SystemGraph) -> Vec<String> {
+        let strangled: Vec<String> = system.graph.node_weights()
+            .filter(|node| is_strangled(node))
+            .map(|node| node.name.clone())
+            .filter(|name| self.replacements.get(name).is_some_and(|successor| system.node(successor).is_some()))
+            .collect();
+        for name in &strangled {
+            let successor = &self.replacements[name];
+            let idx = system.node_indices[name];
+            let successor_idx = system.node_indices[successor];
+            let callers: Vec<_> = system.graph.neighbors_directed(idx, Direction::Incoming).collect();
+            for caller in callers {
+                let caller_name = system.graph[caller].name.clone();
+                if caller == successor_idx || system.graph.find_edge(caller, successor_idx).is_some() {
+                    system.remove_edge(&caller_name, name).expect("edge to legacy node");
+                } else {
+                    system.retarget_edge(&caller_name, name, successor).expect("edge to legacy node");
+                }
+            }
+            system.remove_node(name).expect("legacy node");
+        }
+        strangled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::SystemEdge;
+
+    fn step(migration: &mut ArchitectureMigration, system: &mut SystemGraph, resources: &mut GameResources) -> MigrationProgress {
+        let progress = migration.advance(system, resources).expect("migration is active");
+        resources.sprint += 1;
+        progress
+    }
+
+    #[test]
+    fn test_start_rejects_pointless_migrations() {
+        let mut migration = ArchitectureMigration::default();
+        assert_eq!(
+            migration.start(&ArchitectureType::Monolith.into(), ArchitectureType::Monolith, 3),
+            Err(MigrationError::AlreadyThere(ArchitectureType::Monolith))
+        );
+        migration.start(&ArchitectureType::Monolith.into(), ArchitectureType::Microservices, 3).unwrap();
+        assert_eq!(
+            migration.start(&ArchitectureType::Monolith.into(), ArchitectureType::EventDriven, 3),
+            Err(MigrationError::AlreadyMigrating(ArchitectureType::Microservices))
+        );
+    }
+
+    #[test]
+    fn test_strangler_fig_monolith_to_microservices() {
+        let mut system = create_architecture(ArchitectureType::Monolith);
+        system.node_mut("core_service").unwrap().tech_debt = 60.0;
+        let mut resources = GameResources::default();
+        let mut migration = ArchitectureMigration::default();
+        migration.start(&resources.current_architecture, ArchitectureType::Microservices, 3).unwrap();
+
+        // One target node per sprint, with the legacy node it replaces still running
+        let first = step(&mut migration, &mut system, &mut resources);
+        assert_eq!(first.introduced, vec!["api_gateway"]);
+        assert!(first.decommissioned.is_empty());
+        assert_eq!(system.graph.node_count(), 4);
+        assert_eq!(system.node("api_gateway").unwrap().tech_debt, 60.0);
+        assert!(is_strangled(system.node("core_service").unwrap()));
+        assert_eq!(ArchitectureMigration::dual_running_cost(&system), 250.0);
+
+        // Next sprint the strangled node is switched off
+        let second = step(&mut migration, &mut system, &mut resources);
+        assert_eq!(second.decommissioned, vec!["core_service"]);
+        assert!(system.node("core_service").is_none());
+        let (gateway, auth) = (system.node_indices["api_gateway"], system.node_indices["auth_service"]);
+        assert!(system.graph.find_edge(gateway, auth).is_some());
+
+        step(&mut migration, &mut system, &mut resources);
+        let last = step(&mut migration, &mut system, &mut resources);
+        assert!(last.finished);
+        assert!(!migration.is_active());
+        assert_eq!(resources.current_architecture, ArchitectureType::Microservices.into());
+
+        let mut names: Vec<&str> = system.node_indices.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(names, vec!["api_gateway", "auth_service", "user_service"]);
+        assert_eq!(system.graph.edge_count(), 3);
+        assert_eq!(ArchitectureMigration::dual_running_cost(&system), 0.0);
+    }
+
+    #[test]
+    fn test_merging_away_an_introduced_node() {
+        let mut system = create_architecture(ArchitectureType::Monolith);
+        let mut resources = GameResources::default();
+        let mut migration = ArchitectureMigration::default();
+        migration.start(&resources.current_architecture, ArchitectureType::Microservices, 3).unwrap();
+        step(&mut migration, &mut system, &mut resources);
+        // core_service is strangled by api_gateway, then swallows it
+        system.merge_nodes("core_service", "api_gateway").unwrap();
+
+        let second = step(&mut migration, &mut system, &mut resources);
+        assert!(second.decommissioned.is_empty());
+        assert!(second.introduced.contains(&"api_gateway".to_string()));
+        while migration.is_active() {
+            step(&mut migration, &mut system, &mut resources);
+            assert!(resources.sprint < 10, "migration never finished");
+        }
+        assert!(system.node("core_service").is_none());
+        assert_eq!(resources.current_architecture, ArchitectureType::Microservices.into());
+    }
+
+    #[test]
+    fn test_splitting_a_legacy_node_mid_migration() {
+        let mut system = create_architecture(ArchitectureType::Monolith);
+        let mut resources = GameResources::default();
+        let mut migration = ArchitectureMigration::default();
+        migration.start(&resources.current_architecture, ArchitectureType::Microservices, 3).unwrap();
+        step(&mut migration, &mut system, &mut resources);
+        system.split_node("database", "database_2", &[]).unwrap();
+        system.add_edge("database_2", "api_gateway", SystemEdge::default());
+
+        while migration.is_active() {
+            step(&mut migration, &mut system, &mut resources);
+            assert!(resources.sprint < 10, "migration never finished");
+        }
+        assert!(system.node("database_2").is_none());
+        assert_eq!(resources.current_architecture, ArchitectureType::Microservices.into());
+    }
+
+    #[test]
+    fn test_one_step_per_sprint() {
+        let mut system = create_architecture(ArchitectureType::Monolith);
+        let mut resources = GameResources::default();
+        let mut migration = ArchitectureMigration::default();
+        migration.start(&resources.current_architecture, ArchitectureType::EventDriven, 1).unwrap();
+
+        let progress = migration.advance(&mut system, &mut resources).unwrap();
+        assert_eq!(progress.introduced.len(), 3);
+        assert!(migration.advance(&mut system, &mut resources).is_none());
+        assert_eq!(system.graph.node_count(), 6);
+    }
+}
+
+// Easter egg: "The fig always wins. Eventually. Probably next quarter 🌿"
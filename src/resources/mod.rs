@@ -1,3 +1,46 @@
 mod game_state;
+mod execution_phase;
+mod planned_changes;
+mod simulation;
+mod failure_log;
+mod latency_log;
+mod customers;
+mod defect_tracker;
+mod game_rules;
+mod sprint_report;
+mod save_game;
+mod migration;
+mod graph_history;
 
-pub use game_state::{GameResources, create_initial_system}; 
\ No newline at end of file
+pub use game_state::{GameResources, create_initial_system};
+pub use execution_phase::{ExecutionPhase, PlanningTimer, STEP_APPLY_CHANGES, SPRINT_DURATION_SECS};
+pub use planned_changes::{
+    PlannedChanges,
+    PlannedAction,
+    PlanStatus,
+    PlanningError,
+    ChangeError,
+    apply_actions,
+    execute_actions,
+};
+pub use simulation::{
+    SimulationConfig, SimRng, DEFAULT_SEED, DEFAULT_PLANNING_TIME_LIMIT_SECS, DEFAULT_ARCHITECTURE,
+};
+pub use failure_log::{FailureLog, Incident, IncidentSource};
+pub use latency_log::{LatencyLog, PathLatency, SprintLatency};
+pub use customers::{CustomerModel, TrafficLog, RequestOutcome};
+pub use defect_tracker::{DefectTracker, DEFAULT_ENGINEERING_CAPACITY};
+pub use game_rules::{GameRules, EndReason, FinalScore, ScoreLine, REGULATED_ATTRIBUTE};
+pub use sprint_report::{SprintReport, SprintBaseline, SprintHistory};
+pub use migration::{
+    ArchitectureMigration,
+    MigrationPlan,
+    MigrationProgress,
+    MigrationError,
+    STRANGLED_ATTRIBUTE,
+    DUAL_RUNNING_PENALTY,
+    DEFAULT_MIGRATION_SPRINTS,
+    is_strangled,
+};
+pub use graph_history::{GraphHistory, HistoryEntry};
+pub use save_game::{SaveGame, SaveError, SAVE_VERSION, QUICK_SAVE_PATH};
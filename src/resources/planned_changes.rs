@@ -0,0 +1,436 @@
+use bevy::prelude::*;
+use serde::{Serialize, Deserialize};
+use std::fmt;
+use crate::cards::{DevOpsCard, ActiveEffects, EffectError};
+use crate::components::{SystemGraph, GraphChange, GraphError};
+use super::defect_tracker::DefectTracker;
+
+// A change the player queued during planning
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PlannedAction {
+    PlayCard(DevOpsCard),
+    // Play an Architecture card that reshapes the graph
+    Restructure { card: DevOpsCard, change: GraphChange },
+    // Unwind the lasting effects of a previously played card
+    RetireCard(String),
+}
+
+impl PlannedAction {
+    pub fn card(&self) -> Option<&DevOpsCard> {
+        match self {
+            Self::PlayCard(card) | Self::Restructure { card, .. } => Some(card),
+            Self::RetireCard(_) => None,
+        }
+    }
+
+    pub fn into_card(self) -> Option<DevOpsCard> {
+        match self {
+            Self::PlayCard(card) | Self::Restructure { card, .. } => Some(card),
+            Self::RetireCard(_) => None,
+        }
+    }
+
+    pub fn cost(&self) -> u32 {
+        self.card().map(|card| card.cost).unwrap_or(0)
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::PlayCard(card) => format!("Play {}", card.name),
+            Self::Restructure { card, change } => format!("Play {}: {}", card.name, change.describe()),
+            Self::RetireCard(name) => format!("Retire {}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanningError {
+    Frozen,
+    InsufficientPoints { needed: u32, available: u32 },
+    MissingPrerequisite { card: String, prerequisite: String },
+    NotActive(String),
+    InvalidRestructure(String),
+    Effect(EffectError),
+    Graph(GraphError),
+}
+
+impl fmt::Display for PlanningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Frozen => write!(f, "planning is closed for this sprint"),
+            Self::InsufficientPoints { needed, available } =>
+                write!(f, "needs {} DevOps points but only {} available", needed, available),
+            Self::MissingPrerequisite { card, prerequisite } =>
+                write!(f, "{} requires {} to be played first", card, prerequisite),
+            Self::NotActive(name) => write!(f, "{} has no active effects", name),
+            Self::InvalidRestructure(card) => write!(f, "{} cannot make that structural change", card),
+            Self::Effect(err) => write!(f, "{}", err),
+            Self::Graph(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for PlanningError {}
+
+impl From<EffectError> for PlanningError {
+    fn from(err: EffectError) -> Self {
+        Self::Effect(err)
+    }
+}
+
+impl From<GraphError> for PlanningError {
+    fn from(err: GraphError) -> Self {
+        Self::Graph(err)
+    }
+}
+
+// Failure while applying a batch, pointing at the offending action
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeError {
+    pub action: usize,
+    pub error: PlanningError,
+}
+
+impl fmt::Display for ChangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "planned change #{} failed: {}", self.action + 1, self.error)
+    }
+}
+
+impl std::error::Error for ChangeError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlanStatus {
+    #[default]
+    Open,     // Accepting changes
+    Frozen,   // Planning ended, waiting to be applied
+    Applied,  // Applied this sprint, nothing more to do
+}
+
+// Changes accumulated during planning and applied at the start of execution
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PlannedChanges {
+    actions: Vec<PlannedAction>,
+    status: PlanStatus,
+}
+
+impl PlannedChanges {
+    pub fn actions(&self) -> &[PlannedAction] {
+        &self.actions
+    }
+
+    pub fn status(&self) -> PlanStatus {
+        self.status
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status == PlanStatus::Open
+    }
+
+    pub fn total_cost(&self) -> u32 {
+        self.actions.iter().map(PlannedAction::cost).sum()
+    }
+
+    // Names of the cards queued so far, in order
+    pub fn queued_cards(&self) -> impl Iterator<Item = &str> {
+        self.actions.iter().filter_map(|action| action.card().map(|card| card.name.as_str()))
+    }
+
+    pub fn queue(&mut self, action: PlannedAction, budget: u32) -> Result<(), PlanningError> {
+        if !self.is_open() {
+            return Err(PlanningError::Frozen);
+        }
+        let needed = self.total_cost() + action.cost();
+        if needed > budget {
+            return Err(PlanningError::InsufficientPoints { needed, available: budget });
+        }
+        self.actions.push(action);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<PlannedAction> {
+        if !self.is_open() || index >= self.actions.len() {
+            return None;
+        }
+        Some(self.actions.remove(index))
+    }
+
+    pub fn freeze(&mut self) {
+        if self.status == PlanStatus::Open {
+            self.status = PlanStatus::Frozen;
+        }
+    }
+
+    // Hand out the frozen actions for application. Returns None unless frozen.
+    pub fn take_frozen(&mut self) -> Option<Vec<PlannedAction>> {
+        if self.status != PlanStatus::Frozen {
+            return None;
+        }
+        self.status = PlanStatus::Applied;
+        Some(std::mem::take(&mut self.actions))
+    }
+
+    // Start accepting changes for a new planning phase
+    pub fn reopen(&mut self) {
+        self.status = PlanStatus::Open;
+    }
+}
+
+// Apply all actions or none of them. On error the system and effects are left untouched.
+pub fn apply_actions(
+    system: &mut SystemGraph,
+    effects: &mut ActiveEffects,
+    played: &[String],
+    actions: &[PlannedAction],
+) -> Result<(), ChangeError> {
+    let mut scratch_system = system.clone();
+    let mut scratch_effects = effects.clone();
+    let mut played = played.to_vec();
+
+    for (index, action) in actions.iter().enumerate() {
+        apply_action(&mut scratch_system, &mut scratch_effects, &mut played, action)
+            .map_err(|error| ChangeError { action: index, error })?;
+    }
+
+    *system = scratch_system;
+    *effects = scratch_effects;
+    Ok(())
+}
+
+// What the Apply Changes step does with the queue: every action or none, then
+// the defects the played cards fix outright. Returns each fixing card's name
+// and how many it fixed. On error nothing is touched.
+pub fn execute_actions(
+    system: &mut SystemGraph,
+    effects: &mut ActiveEffects,
+    defects: &mut DefectTracker,
+    played: &[String],
+    actions: &[PlannedAction],
+) -> Result<Vec<(String, u32)>, ChangeError> {
+    apply_actions(system, effects, played, actions)?;
+    Ok(actions.iter()
+        .filter_map(PlannedAction::card)
+        .filter(|card| card.fixes_defects > 0)
+        .map(|card| (card.name.clone(), defects.fix_worst(card.fixes_defects)))
+        .collect())
+}
+
+fn apply_action(
+    system: &mut SystemGraph,
+    effects: &mut ActiveEffects,
+    played: &mut Vec<String>,
+    action: &PlannedAction,
+) -> Result<(), PlanningError> {
+    if let Some(card) = action.card() {
+        if let Some(missing) = card.prerequisites.iter().find(|req| !played.contains(req)) {
+            return Err(PlanningError::MissingPrerequisite {
+                card: card.name.clone(),
+                prerequisite: missing.clone(),
+            });
+        }
+    }
+
+    match action {
+        PlannedAction::PlayCard(card) => {
+            if card.restructure.is_some() {
+                return Err(PlanningError::InvalidRestructure(card.name.clone()));
+            }
+            effects.apply_card(system, card)?;
+            played.push(card.name.clone());
+        }
+        PlannedAction::Restructure { card, change } => {
+            if !card.restructure.is_some_and(|kind| kind.matches(change)) {
+                return Err(PlanningError::InvalidRestructure(card.name.clone()));
+            }
+            system.apply_change(change)?;
+            effects.apply_card(system, card)?;
+            played.push(card.name.clone());
+        }
+        PlannedAction::RetireCard(name) => {
+            if effects.remove_source(system, name) == 0 {
+                return Err(PlanningError::NotActive(name.clone()));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{
+        starter_deck, CardEffect, EffectTarget, EffectDuration, ModificationType,
+        NodeSelector, NodeField,
+    };
+    use crate::test_utils::create_test_graph;
+
+    fn card(name: &str) -> DevOpsCard {
+        starter_deck().into_iter().find(|c| c.name == name).unwrap()
+    }
+
+    fn targeted_card(node: &str) -> DevOpsCard {
+        let mut card = card("Hotfix");
+        card.primary_effect = CardEffect {
+            target: EffectTarget::Node {
+                selector: NodeSelector::Named(node.into()),
+                field: NodeField::TechDebt,
+            },
+            modification: ModificationType::Add,
+            magnitude: -5.0,
+            duration: EffectDuration::Instant,
+        };
+        card.side_effects.clear();
+        card
+    }
+
+    #[test]
+    fn test_queue_respects_budget() {
+        let mut planned = PlannedChanges::default();
+        planned.queue(PlannedAction::PlayCard(card("Add Redundancy")), 5).unwrap();
+        assert_eq!(
+            planned.queue(PlannedAction::PlayCard(card("Refactor System")), 5),
+            Err(PlanningError::InsufficientPoints { needed: 6, available: 5 })
+        );
+        planned.queue(PlannedAction::PlayCard(card("Automate Tests")), 5).unwrap();
+        assert_eq!(planned.total_cost(), 5);
+    }
+
+    #[test]
+    fn test_frozen_plan_rejects_changes() {
+        let mut planned = PlannedChanges::default();
+        planned.queue(PlannedAction::PlayCard(card("Hotfix")), 5).unwrap();
+        planned.freeze();
+
+        assert_eq!(
+            planned.queue(PlannedAction::PlayCard(card("Hotfix")), 5),
+            Err(PlanningError::Frozen)
+        );
+        assert!(planned.remove(0).is_none());
+
+        assert_eq!(planned.take_frozen().map(|a| a.len()), Some(1));
+        assert_eq!(planned.status(), PlanStatus::Applied);
+        assert!(planned.take_frozen().is_none());
+
+        planned.reopen();
+        assert!(planned.is_open());
+        assert!(planned.actions().is_empty());
+    }
+
+    #[test]
+    fn test_apply_actions() {
+        let mut system = create_test_graph();
+        let mut effects = ActiveEffects::default();
+        let actions = vec![
+            PlannedAction::PlayCard(targeted_card("test_node_1")),
+            PlannedAction::PlayCard(card("Automate Tests")),
+        ];
+
+        apply_actions(&mut system, &mut effects, &[], &actions).unwrap();
+        assert_eq!(system.graph[system.node_indices["test_node_1"]].tech_debt, 5.0);
+        assert_eq!(effects.effects.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_change_rolls_back() {
+        let mut system = create_test_graph();
+        let mut effects = ActiveEffects::default();
+        let actions = vec![
+            PlannedAction::PlayCard(targeted_card("test_node_1")),
+            PlannedAction::PlayCard(card("Automate Tests")),
+            PlannedAction::PlayCard(targeted_card("coffee_machine")),
+        ];
+
+        let err = apply_actions(&mut system, &mut effects, &[], &actions).unwrap_err();
+        assert_eq!(err.action, 2);
+        assert_eq!(err.error, PlanningError::Effect(EffectError::UnknownNode("coffee_machine".into())));
+
+        // Nothing from the earlier actions survived
+        assert_eq!(system.graph[system.node_indices["test_node_1"]].tech_debt, 10.0);
+        assert!(effects.effects.is_empty());
+    }
+
+    #[test]
+    fn test_prerequisites_within_batch() {
+        let mut system = create_test_graph();
+        let mut effects = ActiveEffects::default();
+
+        let err = apply_actions(&mut system, &mut effects, &[],
+            &[PlannedAction::PlayCard(card("Implement CI/CD"))]).unwrap_err();
+        assert!(matches!(err.error, PlanningError::MissingPrerequisite { .. }));
+
+        apply_actions(&mut system, &mut effects, &[], &[
+            PlannedAction::PlayCard(card("Automate Tests")),
+            PlannedAction::PlayCard(card("Implement CI/CD")),
+        ]).unwrap();
+    }
+
+    #[test]
+    fn test_restructure_reshapes_graph() {
+        let mut system = crate::components::create_architecture(crate::components::ArchitectureType::Monolith);
+        let mut effects = ActiveEffects::default();
+        let merge = PlannedAction::Restructure {
+            card: card("Consolidate Services"),
+            change: GraphChange::MergeNodes { keep: "database".into(), absorb: "cache".into() },
+        };
+
+        apply_actions(&mut system, &mut effects, &[], &[merge]).unwrap();
+        assert_eq!(system.graph.node_count(), 2);
+        assert!(system.node("cache").is_none());
+
+        // The card only allows merges
+        let split = PlannedAction::Restructure {
+            card: card("Consolidate Services"),
+            change: GraphChange::SplitNode {
+                node: "core_service".into(),
+                new_name: "billing".into(),
+                moved_neighbors: vec![],
+            },
+        };
+        let err = apply_actions(&mut system, &mut effects, &[], &[split]).unwrap_err();
+        assert_eq!(err.error, PlanningError::InvalidRestructure("Consolidate Services".into()));
+
+        // Structural cards can't be played without a change
+        let err = apply_actions(&mut system, &mut effects, &[],
+            &[PlannedAction::PlayCard(card("Consolidate Services"))]).unwrap_err();
+        assert_eq!(err.error, PlanningError::InvalidRestructure("Consolidate Services".into()));
+    }
+
+    #[test]
+    fn test_failed_restructure_rolls_back() {
+        let mut system = create_test_graph();
+        let mut effects = ActiveEffects::default();
+        let actions = vec![
+            PlannedAction::Restructure {
+                card: card("Consolidate Services"),
+                change: GraphChange::MergeNodes { keep: "test_node_1".into(), absorb: "test_node_2".into() },
+            },
+            PlannedAction::Restructure {
+                card: card("Consolidate Services"),
+                change: GraphChange::MergeNodes { keep: "test_node_1".into(), absorb: "test_node_2".into() },
+            },
+        ];
+
+        let err = apply_actions(&mut system, &mut effects, &[], &actions).unwrap_err();
+        assert_eq!(err.action, 1);
+        assert_eq!(err.error, PlanningError::Graph(GraphError::UnknownNode("test_node_2".into())));
+        assert_eq!(system.graph.node_count(), 2);
+    }
+
+    #[test]
+    fn test_retire_card() {
+        let mut system = create_test_graph();
+        let mut effects = ActiveEffects::default();
+        apply_actions(&mut system, &mut effects, &[],
+            &[PlannedAction::PlayCard(card("Automate Tests"))]).unwrap();
+
+        apply_actions(&mut system, &mut effects, &[],
+            &[PlannedAction::RetireCard("Automate Tests".into())]).unwrap();
+        assert!(effects.effects.is_empty());
+
+        let err = apply_actions(&mut system, &mut effects, &[],
+            &[PlannedAction::RetireCard("Automate Tests".into())]).unwrap_err();
+        assert_eq!(err.error, PlanningError::NotActive("Automate Tests".into()));
+    }
+}
+
+// Easter egg: "Change freeze in effect. Except for the coffee machine, which is always deploying ☕"
@@ -0,0 +1,307 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use bevy::prelude::*;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::cards::{Deck, ActiveEffects};
+use crate::components::SystemGraph;
+use super::game_state::GameResources;
+use super::defect_tracker::DefectTracker;
+use super::planned_changes::{PlannedChanges, PlannedAction};
+use super::simulation::SimRng;
+use super::migration::ArchitectureMigration;
+use super::graph_history::GraphHistory;
+use super::sprint_report::SprintHistory;
+
+// Bump this and add a migration whenever the save format changes
+pub const SAVE_VERSION: u32 = 2;
+pub const QUICK_SAVE_PATH: &str = "quicksave.json";
+
+// Upgrades a save by exactly one version
+type Migration = fn(Value) -> Value;
+
+// MIGRATIONS[n] turns a version n + 1 save into version n + 2
+const MIGRATIONS: &[Migration] = &[add_architecture_migration];
+
+// v2: architecture migrations in progress are saved
+fn add_architecture_migration(mut value: Value) -> Value {
+    value["migration"] = serde_json::to_value(ArchitectureMigration::default()).expect("plain data");
+    value
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Format(serde_json::Error),
+    MissingVersion,
+    UnsupportedVersion(u64),
+    NoSystem,
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not access save file: {}", err),
+            Self::Format(err) => write!(f, "save file is malformed: {}", err),
+            Self::MissingVersion => write!(f, "save file has no version field"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "save version {} is not supported (latest is {})", version, SAVE_VERSION)
+            }
+            Self::NoSystem => write!(f, "there is no system to save"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Format(err)
+    }
+}
+
+// Everything needed to pick a game back up at the start of a planning phase.
+// Per-sprint logs aren't saved; they start fresh with the next sprint anyway,
+// and the graph and sprint histories restart from the loaded system.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub seed: u64,
+    pub rng_position: u128,
+    pub resources: GameResources,  // Includes the sprint number
+    pub system: SystemGraph,
+    pub deck: Deck,
+    pub effects: ActiveEffects,
+    pub defects: DefectTracker,
+    pub migration: ArchitectureMigration,
+}
+
+impl SaveGame {
+    pub fn capture(world: &mut World) -> Result<Self, SaveError> {
+        let system = world.query::<&SystemGraph>()
+            .iter(world)
+            .next()
+            .cloned()
+            .ok_or(SaveError::NoSystem)?;
+
+        // Cards queued but not yet played go back into the saved hand
+        let mut deck = world.resource::<Deck>().clone();
+        if let Some(planned) = world.get_resource::<PlannedChanges>() {
+            deck.hand.extend(planned.actions().iter().cloned().filter_map(PlannedAction::into_card));
+        }
+
+        let rng = world.resource::<SimRng>();
+        Ok(Self {
+            version: SAVE_VERSION,
+            seed: rng.seed(),
+            rng_position: rng.position(),
+            resources: world.resource::<GameResources>().clone(),
+            system,
+            deck,
+            effects: world.resource::<ActiveEffects>().clone(),
+            defects: world.resource::<DefectTracker>().clone(),
+            migration: world.get_resource::<ArchitectureMigration>().cloned().unwrap_or_default(),
+        })
+    }
+
+    // Replace the running game with this save. Anything queued for the
+    // current planning phase is dropped; the saved hand already has it.
+    // History isn't saved, so it starts over rather than mixing two runs.
+    pub fn restore(self, world: &mut World) {
+        let mut history = GraphHistory::default();
+        history.begin(&self.system);
+        world.insert_resource(history);
+        world.insert_resource(SprintHistory::default());
+
+        let mut systems = world.query::<&mut SystemGraph>();
+        match systems.iter_mut(world).next() {
+            Some(mut system) => *system = self.system,
+            None => {
+                world.spawn(self.system);
+            }
+        }
+        world.insert_resource(SimRng::restore(self.seed, self.rng_position));
+        world.insert_resource(self.resources);
+        world.insert_resource(self.deck);
+        world.insert_resource(self.effects);
+        world.insert_resource(self.defects);
+        world.insert_resource(self.migration);
+        world.insert_resource(PlannedChanges::default());
+    }
+
+    pub fn to_json(&self) -> Result<String, SaveError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    // Older saves are migrated up to the current version before being read
+    pub fn from_json(json: &str) -> Result<Self, SaveError> {
+        let value = migrate(serde_json::from_str(json)?, MIGRATIONS)?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SaveError> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SaveError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+}
+
+fn migrate(mut value: Value, migrations: &[Migration]) -> Result<Value, SaveError> {
+    let latest = migrations.len() as u64 + 1;
+    let version = value.get("version")
+        .and_then(Value::as_u64)
+        .ok_or(SaveError::MissingVersion)?;
+    if version == 0 || version > latest {
+        return Err(SaveError::UnsupportedVersion(version));
+    }
+
+    for migration in &migrations[version as usize - 1..] {
+        value = migration(value);
+    }
+    value["version"] = Value::from(latest);
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+    use crate::components::{Severity, GraphDiff};
+    use crate::test_utils::create_test_graph;
+
+    fn game_world() -> World {
+        let mut world = World::new();
+        world.insert_resource(GameResources { money: 4321.0, sprint: 7, ..Default::default() });
+        world.insert_resource(Deck::default());
+        world.insert_resource(ActiveEffects::default());
+        world.insert_resource(DefectTracker::default());
+        world.insert_resource(PlannedChanges::default());
+        world.insert_resource(SimRng::new(42));
+        world.spawn(create_test_graph());
+        world
+    }
+
+    #[test]
+    fn test_versions_match_migrations() {
+        assert_eq!(SAVE_VERSION as usize, MIGRATIONS.len() + 1);
+    }
+
+    #[test]
+    fn test_save_and_restore_round_trip() {
+        let mut world = game_world();
+        world.resource_mut::<DefectTracker>().open("test_node_2", Severity::High, 6);
+        world.resource_mut::<SimRng>().gen::<u64>();
+        let saved = SaveGame::capture(&mut world).unwrap();
+
+        let json = saved.to_json().unwrap();
+        assert!(json.contains("\"version\": 2"));
+        let loaded = SaveGame::from_json(&json).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), json);
+
+        // Restore into a world that has moved on
+        let mut other = game_world();
+        other.resource_mut::<GameResources>().money = -1.0;
+        other.query::<&mut SystemGraph>().single_mut(&mut other).remove_node("test_node_2").unwrap();
+        loaded.restore(&mut other);
+
+        assert_eq!(other.resource::<GameResources>().sprint, 7);
+        assert_eq!(other.resource::<GameResources>().money, 4321.0);
+        assert_eq!(other.resource::<DefectTracker>().open_on("test_node_2"), 1);
+        assert_eq!(other.query::<&SystemGraph>().single(&other).graph.node_count(), 2);
+        // The restored RNG carries on where the saved one left off
+        let next: u64 = world.resource_mut::<SimRng>().gen();
+        assert_eq!(other.resource_mut::<SimRng>().gen::<u64>(), next);
+    }
+
+    #[test]
+    fn test_restore_restarts_history_from_the_loaded_system() {
+        use crate::resources::{SprintReport, SprintBaseline, FailureLog, TrafficLog};
+
+        let mut world = game_world();
+        let saved = SaveGame::capture(&mut world).unwrap();
+
+        // Play on past the save, then load it again
+        let mut system = create_test_graph();
+        let mut history = GraphHistory::default();
+        history.begin(&system);
+        system.remove_node("test_node_2").unwrap();
+        history.record(7, &system);
+        history.record(8, &system);
+        let report = SprintReport::new(
+            &SprintBaseline::default(),
+            world.resource::<GameResources>(),
+            &system,
+            &FailureLog::default(),
+            &DefectTracker::default(),
+            &TrafficLog::default(),
+        );
+        world.insert_resource(history);
+        world.insert_resource(SprintHistory { reports: vec![report] });
+        saved.clone().restore(&mut world);
+
+        let history = world.resource::<GraphHistory>();
+        assert_eq!(history.len(), 1);
+        assert!(GraphDiff::between(&history.graph_at(0).unwrap(), &saved.system).is_empty());
+        assert!(world.resource::<SprintHistory>().reports.is_empty());
+    }
+
+    #[test]
+    fn test_queued_cards_are_saved_in_hand() {
+        let mut world = game_world();
+        let card = world.resource::<Deck>().draw_pile[0].clone();
+        world.resource_mut::<PlannedChanges>().queue(PlannedAction::PlayCard(card.clone()), 100).unwrap();
+
+        let saved = SaveGame::capture(&mut world).unwrap();
+        assert_eq!(saved.deck.hand, vec![card]);
+    }
+
+    #[test]
+    fn test_migrations_run_in_order() {
+        fn add_field(mut value: Value) -> Value {
+            value["added"] = Value::from(true);
+            value
+        }
+        fn rename_field(mut value: Value) -> Value {
+            let added = value["added"].take();
+            value["renamed"] = added;
+            value
+        }
+        let migrations: &[Migration] = &[add_field, rename_field];
+
+        let migrated = migrate(serde_json::json!({ "version": 1 }), migrations).unwrap();
+        assert_eq!(migrated["version"], 3);
+        assert_eq!(migrated["renamed"], true);
+
+        // Already partway there: only the remaining step runs
+        let migrated = migrate(serde_json::json!({ "version": 2, "added": 5 }), migrations).unwrap();
+        assert_eq!(migrated["renamed"], 5);
+
+        assert!(matches!(migrate(serde_json::json!({ "version": 4 }), migrations), Err(SaveError::UnsupportedVersion(4))));
+        assert!(matches!(migrate(serde_json::json!({}), migrations), Err(SaveError::MissingVersion)));
+    }
+
+    #[test]
+    fn test_version_1_saves_still_load() {
+        let mut world = game_world();
+        let mut value = serde_json::to_value(SaveGame::capture(&mut world).unwrap()).unwrap();
+        value["version"] = Value::from(1);
+        value.as_object_mut().unwrap().remove("migration");
+
+        let loaded = SaveGame::from_json(&value.to_string()).unwrap();
+        assert_eq!(loaded.version, SAVE_VERSION);
+        assert!(!loaded.migration.is_active());
+    }
+}
+
+// Easter egg: "Saved. Unlike the last three production databases 💾"
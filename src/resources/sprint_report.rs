@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+use crate::components::SystemGraph;
+use super::game_state::GameResources;
+use super::failure_log::FailureLog;
+use super::defect_tracker::DefectTracker;
+use super::customers::TrafficLog;
+
+// Where the company stood when the sprint started, so the report can show deltas
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct SprintBaseline {
+    pub money: f64,
+    pub reputation: f64,
+    pub average_tech_debt: f64,
+}
+
+impl SprintBaseline {
+    pub fn capture(resources: &GameResources, system: Option<&SystemGraph>) -> Self {
+        Self {
+            money: resources.money,
+            reputation: resources.reputation,
+            average_tech_debt: system.map_or(0.0, SystemGraph::average_tech_debt),
+        }
+    }
+}
+
+// What happened during one sprint. Sent as an event when execution ends and
+// shown in the sprint review before the next planning phase.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct SprintReport {
+    pub sprint: u32,
+    pub money: f64,
+    pub money_delta: f64,
+    pub reputation: f64,
+    pub reputation_delta: f64,
+    pub revenue: f64,  // Net customer revenue, already part of money_delta
+    pub incidents: usize,
+    pub defects_found: u32,
+    pub defects_fixed: u32,
+    pub defects_open: usize,
+    pub average_tech_debt: f64,
+    pub tech_debt_delta: f64,
+}
+
+impl SprintReport {
+    pub fn new(
+        baseline: &SprintBaseline,
+        resources: &GameResources,
+        system: &SystemGraph,
+        failures: &FailureLog,
+        defects: &DefectTracker,
+        traffic: &TrafficLog,
+    ) -> Self {
+        let average_tech_debt = system.average_tech_debt();
+        Self {
+            sprint: resources.sprint,
+            money: resources.money,
+            money_delta: resources.money - baseline.money,
+            reputation: resources.reputation,
+            reputation_delta: resources.reputation - baseline.reputation,
+            revenue: traffic.net_revenue(),
+            incidents: failures.incidents.len(),
+            defects_found: defects.found_this_sprint,
+            defects_fixed: defects.fixed_this_sprint,
+            defects_open: defects.open_count(),
+            average_tech_debt,
+            tech_debt_delta: average_tech_debt - baseline.average_tech_debt,
+        }
+    }
+}
+
+// Every sprint report so far, oldest first
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SprintHistory {
+    pub reports: Vec<SprintReport>,
+}
+
+impl SprintHistory {
+    pub fn latest(&self) -> Option<&SprintReport> {
+        self.reports.last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Severity;
+    use crate::test_utils::create_test_graph;
+
+    #[test]
+    fn test_report_deltas() {
+        let mut system = create_test_graph();
+        let start = GameResources::default();
+        let baseline = SprintBaseline::capture(&start, Some(&system));
+
+        system.node_mut("test_node_2").unwrap().tech_debt += 20.0;
+        let end = GameResources { money: start.money - 750.0, reputation: 47.5, ..Default::default() };
+        let mut defects = DefectTracker::default();
+        defects.open("test_node_1", Severity::Low, 1);
+
+        let report = SprintReport::new(&baseline, &end, &system, &FailureLog::default(), &defects, &TrafficLog::default());
+        assert_eq!(report.sprint, 1);
+        assert_eq!(report.money_delta, -750.0);
+        assert_eq!(report.reputation_delta, -2.5);
+        assert_eq!(report.tech_debt_delta, 10.0);
+        assert_eq!((report.defects_found, report.defects_open), (1, 1));
+        assert_eq!(report.incidents, 0);
+    }
+}
+
+// Easter egg: "Sprint velocity: 13 story points, 8 of them spent on the retro 📈"
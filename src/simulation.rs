@@ -0,0 +1,238 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use crate::cards::ActiveEffects;
+use crate::components::SystemGraph;
+use crate::plugins::SimulationPlugin;
+use crate::resources::{GameResources, SimulationConfig, EndReason, FinalScore, DefectTracker, ArchitectureMigration};
+use crate::GameState;
+
+// Upper bound on frames per sprint before a run is considered stuck
+const MAX_UPDATES_PER_SPRINT: usize = 100_000;
+
+// Where a headless run ended up
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationSummary {
+    pub seed: u64,
+    pub sprints: u32,
+    pub updates: usize,
+    pub money: f64,
+    pub reputation: f64,
+    pub average_tech_debt: f64,
+    pub average_health: f64,
+    pub outcome: Option<EndReason>,  // Set once the game has been won or lost
+}
+
+// Runs the game without a window or GPU: every planning phase and sprint review
+// is skipped straight through and each frame advances exactly one simulation tick.
+//
+//     let summary = Simulation::new(SimulationConfig::with_seed(7)).run_sprints(20);
+pub struct Simulation {
+    app: App,
+    seed: u64,
+    updates: usize,
+    sprints: u32,
+}
+
+impl Simulation {
+    pub fn new(config: SimulationConfig) -> Self {
+        let seed = config.seed;
+        let tick_period = config.tick_period();
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(SimulationPlugin { config })
+            .insert_resource(TimeUpdateStrategy::ManualDuration(tick_period))
+            .add_systems(Update, (
+                start_sprint.run_if(in_state(GameState::Planning)),
+                close_review.run_if(in_state(GameState::SprintReview)),
+            ));
+
+        Self { app, seed, updates: 0, sprints: 0 }
+    }
+
+    // Run with a custom architecture instead of the default initial system
+    pub fn with_system(mut self, system: SystemGraph) -> Self {
+        self.app.world.spawn(system);
+        self
+    }
+
+    pub fn with_resources(mut self, resources: GameResources) -> Self {
+        self.app.insert_resource(resources);
+        self
+    }
+
+    // Start with defects already open, e.g. those of a game in progress
+    pub fn with_defects(mut self, defects: DefectTracker) -> Self {
+        self.app.insert_resource(defects);
+        self
+    }
+
+    pub fn with_migration(mut self, migration: ArchitectureMigration) -> Self {
+        self.app.insert_resource(migration);
+        self
+    }
+
+    pub fn with_effects(mut self, effects: ActiveEffects) -> Self {
+        self.app.insert_resource(effects);
+        self
+    }
+
+    // Direct access for callers that want to queue cards or inspect state between sprints
+    pub fn world(&self) -> &World {
+        &self.app.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.app.world
+    }
+
+    pub fn resources(&self) -> &GameResources {
+        self.app.world.resource::<GameResources>()
+    }
+
+    pub fn system(&mut self) -> Option<&SystemGraph> {
+        self.app.world.query::<&SystemGraph>().iter(&self.app.world).next()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.app.world.resource::<State<GameState>>().get().is_finished()
+    }
+
+    // Play out up to `count` more sprints and report where the game stands.
+    // Stops early if the game is won or lost along the way.
+    pub fn run_sprints(&mut self, count: u32) -> SimulationSummary {
+        for _ in 0..count {
+            if self.is_finished() {
+                break;
+            }
+            self.run_sprint();
+        }
+        self.summary()
+    }
+
+    fn run_sprint(&mut self) {
+        let mut was_running = false;
+        for _ in 0..MAX_UPDATES_PER_SPRINT {
+            self.app.update();
+            self.updates += 1;
+
+            match self.app.world.resource::<State<GameState>>().get() {
+                GameState::Running => was_running = true,
+                GameState::Planning if was_running => {
+                    self.sprints += 1;
+                    return;
+                }
+                state if state.is_finished() => {
+                    self.sprints += 1;
+                    return;
+                }
+                _ => {}
+            }
+        }
+        panic!("sprint {} did not finish within {} updates", self.sprints + 1, MAX_UPDATES_PER_SPRINT);
+    }
+
+    pub fn summary(&mut self) -> SimulationSummary {
+        let (average_tech_debt, average_health) = match self.system() {
+            Some(system) if system.graph.node_count() > 0 => {
+                let count = system.graph.node_count() as f64;
+                let health = system.graph.node_weights().map(|n| n.health).sum::<f64>() / count;
+                (system.average_tech_debt(), health)
+            }
+            _ => (0.0, 0.0),
+        };
+        let outcome = self.app.world.get_resource::<FinalScore>().map(|score| score.reason);
+        let resources = self.resources();
+        SimulationSummary {
+            seed: self.seed,
+            sprints: self.sprints,
+            updates: self.updates,
+            money: resources.money,
+            reputation: resources.reputation,
+            average_tech_debt,
+            average_health,
+            outcome,
+        }
+    }
+}
+
+// Nobody is at the keyboard: start executing as soon as planning opens
+fn start_sprint(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::Running);
+}
+
+// Nobody reads the report either; it is still in `SprintHistory`
+fn close_review(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::Planning);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_graph;
+
+    #[test]
+    fn test_runs_requested_sprints() {
+        use crate::resources::{GameRules, SprintHistory};
+
+        let config = SimulationConfig { rules: GameRules::endless(), ..SimulationConfig::with_seed(1) };
+        let mut sim = Simulation::new(config);
+        let summary = sim.run_sprints(3);
+
+        assert_eq!(summary.sprints, 3);
+        assert_eq!(summary.seed, 1);
+        assert!(summary.money < GameResources::default().money, "operating costs should be paid");
+
+        let more = sim.run_sprints(2);
+        assert_eq!(more.sprints, 5);
+        assert!(more.updates > summary.updates);
+        assert_eq!(more.outcome, None);
+
+        let history = sim.world().resource::<SprintHistory>();
+        let sprints: Vec<u32> = history.reports.iter().map(|report| report.sprint).collect();
+        assert_eq!(sprints, vec![1, 2, 3, 4, 5]);
+        assert_eq!(sim.resources().sprint, 6);
+    }
+
+    #[test]
+    fn test_run_stops_when_game_is_lost() {
+        let broke = GameResources { money: -1.0e6, ..Default::default() };
+        let mut sim = Simulation::new(SimulationConfig::with_seed(3)).with_resources(broke);
+        let summary = sim.run_sprints(5);
+
+        assert!(sim.is_finished());
+        assert_eq!(summary.sprints, 1);
+        assert_eq!(summary.outcome, Some(EndReason::Bankrupt));
+        assert_eq!(sim.run_sprints(5), summary);
+    }
+
+    #[test]
+    fn test_same_seed_same_summary() {
+        let a = Simulation::new(SimulationConfig::with_seed(11)).run_sprints(2);
+        let b = Simulation::new(SimulationConfig::with_seed(11)).run_sprints(2);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_sprint_runs_configured_ticks() {
+        use crate::resources::ExecutionPhase;
+
+        let config = SimulationConfig { ticks_per_sprint: 25, ..Default::default() };
+        let mut sim = Simulation::new(config);
+        sim.run_sprints(1);
+        assert_eq!(sim.world().resource::<ExecutionPhase>().ticks, 25);
+    }
+
+    #[test]
+    fn test_custom_system_is_used() {
+        let graph = create_test_graph();
+        let nodes = graph.graph.node_count();
+        let mut sim = Simulation::new(SimulationConfig::default()).with_system(graph);
+        sim.run_sprints(1);
+
+        let system = sim.system().expect("system spawned");
+        assert_eq!(system.graph.node_count(), nodes);
+        assert_eq!(sim.world_mut().query::<&SystemGraph>().iter(sim.world()).count(), 1);
+    }
+}
+
+// Easter egg: "No GPUs were harmed in the making of this balance pass 🖥️"
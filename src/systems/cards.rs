@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use crate::cards::{Deck, ActiveEffects};
+use crate::components::SystemGraph;
+use crate::resources::SimRng;
+
+// Discard the previous hand and draw a fresh one at the start of planning
+pub fn draw_hand(mut deck: ResMut<Deck>, mut rng: ResMut<SimRng>) {
+    let drawn = deck.draw_hand(rng.as_mut());
+    info!("Drew {} cards ({} left in draw pile, {} in discard)",
+        drawn, deck.draw_pile.len(), deck.discard_pile.len());
+}
+
+// Count down timed card effects and unwind the ones that have run their course
+pub fn expire_effects(
+    mut active: ResMut<ActiveEffects>,
+    mut query: Query<&mut SystemGraph>,
+) {
+    if let Ok(mut system) = query.get_single_mut() {
+        for effect in active.end_sprint(&mut system) {
+            info!("Effect from {} expired ({} changes reverted)",
+                effect.source, effect.changes.len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::DEFAULT_HAND_SIZE;
+    use crate::GameState;
+
+    #[test]
+    fn test_hand_drawn_on_enter_planning() {
+        let mut app = App::new();
+        app.add_state::<GameState>()
+            .init_resource::<Deck>()
+            .init_resource::<SimRng>()
+            .add_systems(OnEnter(GameState::Planning), draw_hand);
+
+        app.world.resource_mut::<NextState<GameState>>().set(GameState::Planning);
+        app.update();
+
+        let deck = app.world.resource::<Deck>();
+        assert_eq!(deck.hand.len(), DEFAULT_HAND_SIZE);
+    }
+
+    #[test]
+    fn test_same_seed_draws_same_hand() {
+        let hand_for = |seed: u64| {
+            let mut app = App::new();
+            app.add_state::<GameState>()
+                .init_resource::<Deck>()
+                .insert_resource(SimRng::new(seed))
+                .add_systems(OnEnter(GameState::Planning), draw_hand);
+            app.world.resource_mut::<Deck>().shuffle(&mut SimRng::new(seed));
+            app.world.resource_mut::<NextState<GameState>>().set(GameState::Planning);
+            app.update();
+            app.world.resource::<Deck>().hand.iter().map(|c| c.name.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(hand_for(2024), hand_for(2024));
+    }
+}
+
+// Easter egg: "Always draw to an inside straight when the pager is quiet 🂡"
@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use crate::components::{SystemGraph, Severity};
+use crate::resources::{GameResources, DefectTracker, SimulationConfig, SimRng};
+
+// Clear fixed defects off the board before a new sprint starts
+pub fn start_defect_tracking(mut tracker: ResMut<DefectTracker>) {
+    tracker.start_sprint();
+}
+
+// The "Process Defects" step, one tick at a time: find new defects, let the open
+// ones chew on their node's health, and spend engineering capacity fixing them
+pub fn defect_system(
+    config: Res<SimulationConfig>,
+    resources: Res<GameResources>,
+    mut rng: ResMut<SimRng>,
+    mut tracker: ResMut<DefectTracker>,
+    mut query: Query<&mut SystemGraph>,
+) {
+    let Ok(mut system) = query.get_single_mut() else {
+        return;
+    };
+    let dt = config.tick_secs();
+    let sprint_fraction = 1.0 / config.ticks_per_sprint.max(1) as f64;
+
+    tracker.retire_missing(|name| system.node(name).is_some());
+
+    for (name, count) in system.generate_defects(sprint_fraction, rng.as_mut()) {
+        let Some(node) = system.node(&name) else {
+            continue;
+        };
+        for _ in 0..count {
+            let severity = Severity::roll(node, rng.as_mut());
+            tracker.open(&name, severity, resources.sprint);
+            debug!("{} {} defect found in {}", severity.icon(), severity.name(), name);
+        }
+    }
+
+    for defect in tracker.defects.iter_mut().filter(|d| d.is_open()) {
+        defect.age += dt;
+        if let Some(node) = system.node_mut(&defect.origin) {
+            node.health = (node.health - defect.severity.health_damage() * dt).max(0.0);
+        }
+    }
+
+    let effort = tracker.capacity_per_sprint * sprint_fraction;
+    let fixed = tracker.apply_effort(effort);
+    if fixed > 0 {
+        debug!("Engineering fixed {} defects ({} still open)", fixed, tracker.open_count());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_graph;
+
+    fn setup_app(defect_rate: f64, capacity: f64) -> App {
+        let mut system = create_test_graph();
+        for node in system.graph.node_weights_mut() {
+            node.defect_rate = defect_rate;
+        }
+
+        let mut app = App::new();
+        app.insert_resource(SimulationConfig { ticks_per_sprint: 10, ..Default::default() })
+            .insert_resource(DefectTracker::with_capacity(capacity))
+            .init_resource::<SimRng>()
+            .init_resource::<GameResources>()
+            .add_systems(Update, defect_system);
+        app.world.spawn(system);
+        app
+    }
+
+    #[test]
+    fn test_open_defects_hurt_health() {
+        let mut app = setup_app(0.0, 0.0);
+        app.world.resource_mut::<DefectTracker>().open("test_node_2", Severity::Critical, 1);
+        app.update();
+
+        let tracker = app.world.resource::<DefectTracker>();
+        assert_eq!(tracker.open_count(), 1);
+        assert_eq!(tracker.defects[0].age, 1.0);
+
+        let system = app.world.query::<&SystemGraph>().single(&app.world);
+        assert_eq!(system.node("test_node_2").unwrap().health, 100.0 - Severity::Critical.health_damage());
+        assert_eq!(system.node("test_node_1").unwrap().health, 100.0);
+    }
+
+    #[test]
+    fn test_defects_found_and_fixed_over_a_sprint() {
+        let mut app = setup_app(5.0, 1000.0);
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let tracker = app.world.resource::<DefectTracker>();
+        assert!(tracker.found_this_sprint > 0);
+        // Plenty of capacity: everything found gets fixed the same tick
+        assert_eq!(tracker.fixed_this_sprint, tracker.found_this_sprint);
+        assert_eq!(tracker.open_count(), 0);
+    }
+
+    #[test]
+    fn test_removed_node_retires_its_defects() {
+        let mut app = setup_app(0.0, 0.0);
+        app.world.resource_mut::<DefectTracker>().open("test_node_2", Severity::High, 1);
+        app.world.query::<&mut SystemGraph>().single_mut(&mut app.world)
+            .remove_node("test_node_2").unwrap();
+        app.update();
+
+        assert_eq!(app.world.resource::<DefectTracker>().open_count(), 0);
+    }
+}
+
+// Easter egg: "Found in production, reproduced in production, fixed in production 🔁"
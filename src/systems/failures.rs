@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use crate::components::SystemGraph;
+use crate::resources::{GameResources, ExecutionPhase, FailureLog, SimulationConfig, SimRng};
+
+// Health a component loses each time it fails on its own
+pub const FAILURE_HEALTH_LOSS: f64 = 5.0;
+
+// Reputation lost per simulated second for each critical path node that is down
+pub const OUTAGE_REPUTATION_LOSS: f64 = 0.5;
+
+// A new sprint starts with a clean incident log and everything up
+pub fn start_failure_log(mut log: ResMut<FailureLog>) {
+    log.start_sprint();
+}
+
+// Roll failures for one fixed tick and cascade them through the dependency graph
+pub fn failure_system(
+    config: Res<SimulationConfig>,
+    phase: Res<ExecutionPhase>,
+    mut rng: ResMut<SimRng>,
+    mut log: ResMut<FailureLog>,
+    mut resources: ResMut<GameResources>,
+    mut query: Query<&mut SystemGraph>,
+) {
+    let Ok(mut system) = query.get_single_mut() else {
+        return;
+    };
+    let dt = config.tick_secs();
+    let report = log.outages.step(&system, dt, rng.as_mut());
+
+    for name in &report.failed_nodes {
+        if let Some(node) = system.node_mut(name) {
+            node.health = (node.health - FAILURE_HEALTH_LOSS).max(0.0);
+        }
+        warn!("💥 {} failed, taking down {:?}", name, report.down);
+    }
+    for (from, to) in &report.failed_links {
+        warn!("🔌 Link {} -> {} broke", from, to);
+    }
+
+    let critical_down = report.down.iter()
+        .filter(|name| system.node(name).is_some_and(|node| node.critical_path))
+        .count();
+    resources.reputation -= critical_down as f64 * OUTAGE_REPUTATION_LOSS * dt;
+
+    log.record(&report, dt, phase.elapsed_time);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::DistributionType;
+    use crate::test_utils::create_test_graph;
+
+    #[test]
+    fn test_critical_outage_costs_reputation() {
+        let mut system = create_test_graph();
+        // test_node_1 depends on test_node_2, which always fails
+        system.node_mut("test_node_2").unwrap().failure_rate =
+            DistributionType::Normal { mean: 1.0e6, std_dev: 0.0 };
+        system.node_mut("test_node_1").unwrap().failure_rate =
+            DistributionType::Normal { mean: 0.0, std_dev: 0.0 };
+
+        let mut app = App::new();
+        app.insert_resource(SimulationConfig { ticks_per_sprint: 10, ..Default::default() })
+            .init_resource::<ExecutionPhase>()
+            .init_resource::<SimRng>()
+            .init_resource::<FailureLog>()
+            .init_resource::<GameResources>()
+            .add_systems(Update, failure_system);
+        app.world.spawn(system);
+        app.update();
+
+        let log = app.world.resource::<FailureLog>();
+        assert!(log.is_down("test_node_1"));
+        assert_eq!(log.downtime["test_node_1"], 1.0);
+
+        // One critical node down for one simulated second
+        let reputation = app.world.resource::<GameResources>().reputation;
+        assert_eq!(reputation, GameResources::default().reputation - OUTAGE_REPUTATION_LOSS);
+
+        let system = app.world.query::<&SystemGraph>().single(&app.world);
+        assert_eq!(system.node("test_node_2").unwrap().health, 100.0 - FAILURE_HEALTH_LOSS);
+    }
+}
+
+// Easter egg: "The cascade always starts at the one service nobody owns 🧯"
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use crate::components::SystemGraph;
+use crate::resources::{GraphHistory, SprintReport};
+
+// The first planning phase takes the snapshot the timeline starts from
+pub fn start_graph_history(mut history: ResMut<GraphHistory>, query: Query<&SystemGraph>) {
+    if let Ok(system) = query.get_single() {
+        history.begin(system);
+    }
+}
+
+// Sprint end: record what the sprint did to the system, won or lost
+pub fn record_graph_history(
+    mut reports: EventReader<SprintReport>,
+    mut history: ResMut<GraphHistory>,
+    query: Query<&SystemGraph>,
+) {
+    let Ok(system) = query.get_single() else {
+        return;
+    };
+    for report in reports.iter() {
+        history.record(report.sprint, system);
+        debug!("Recorded {} system changes in sprint {}",
+            history.entries().last().map_or(0, |entry| entry.diff.changes.len()), report.sprint);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::GraphDiff;
+    use crate::resources::{GraphHistory, GameRules, SimulationConfig};
+    use crate::simulation::Simulation;
+
+    #[test]
+    fn test_every_sprint_is_recorded() {
+        let config = SimulationConfig { rules: GameRules::endless(), ..SimulationConfig::with_seed(4) };
+        let mut sim = Simulation::new(config);
+        sim.run_sprints(3);
+
+        let history = sim.world().resource::<GraphHistory>().clone();
+        assert_eq!(history.entries().iter().map(|entry| entry.sprint).collect::<Vec<_>>(), vec![1, 2, 3]);
+        // Debt and health drift every sprint
+        assert!(history.entries().iter().all(|entry| !entry.diff.is_empty()));
+        let system = sim.system().unwrap();
+        assert!(GraphDiff::between(&history.graph_at(3).unwrap(), system).is_empty());
+    }
+}
+
+// Easter egg: "Every incident review starts with 'so, going back to sprint 3...' 🕰️"
@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+use crate::components::{SystemGraph, request_paths, path_latency};
+use crate::resources::{GameResources, FailureLog, LatencyLog, SimulationConfig, SimRng};
+
+// Requests sampled along every path each tick
+pub const REQUESTS_PER_TICK: usize = 10;
+
+// Reputation lost per simulated second when every request is slower than the SLO
+pub const SLO_REPUTATION_LOSS: f64 = 1.0;
+
+pub fn start_latency_log(mut log: ResMut<LatencyLog>) {
+    log.start_sprint();
+}
+
+// Send a handful of requests down every path and see how long they take.
+// Requests through a component that is down fail instead.
+pub fn latency_system(
+    config: Res<SimulationConfig>,
+    failures: Res<FailureLog>,
+    mut rng: ResMut<SimRng>,
+    mut log: ResMut<LatencyLog>,
+    mut resources: ResMut<GameResources>,
+    query: Query<&SystemGraph>,
+) {
+    let Ok(system) = query.get_single() else {
+        return;
+    };
+
+    let mut total = 0;
+    let mut slow = 0;
+    for path in request_paths(system) {
+        let entry = log.path_mut(&path);
+        if path.iter().any(|name| failures.is_down(name)) {
+            entry.failed += REQUESTS_PER_TICK;
+            continue;
+        }
+        for _ in 0..REQUESTS_PER_TICK {
+            let latency = path_latency(system, &path, rng.as_mut());
+            if latency > config.latency_slo_ms {
+                entry.slow += 1;
+                slow += 1;
+            }
+            entry.samples.push(latency);
+            total += 1;
+        }
+    }
+
+    if total > 0 {
+        let slow_fraction = slow as f64 / total as f64;
+        resources.reputation -= slow_fraction * SLO_REPUTATION_LOSS * config.tick_secs();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::DistributionType;
+    use crate::test_utils::create_test_graph;
+
+    fn run_tick(latency_ms: f64) -> App {
+        let mut system = create_test_graph();
+        for node in system.graph.node_weights_mut() {
+            node.latency = DistributionType::Normal { mean: latency_ms, std_dev: 0.0 };
+            node.tech_debt = 0.0;
+        }
+        for edge in system.graph.edge_weights_mut() {
+            edge.latency = DistributionType::Normal { mean: 0.0, std_dev: 0.0 };
+        }
+
+        let mut app = App::new();
+        app.insert_resource(SimulationConfig { ticks_per_sprint: 10, ..Default::default() })
+            .init_resource::<SimRng>()
+            .init_resource::<FailureLog>()
+            .init_resource::<LatencyLog>()
+            .init_resource::<GameResources>()
+            .add_systems(Update, latency_system);
+        app.world.spawn(system);
+        app.update();
+        app
+    }
+
+    #[test]
+    fn test_fast_requests_keep_reputation() {
+        let app = run_tick(100.0);
+        let log = app.world.resource::<LatencyLog>();
+        assert_eq!(log.paths.len(), 1);
+        assert_eq!(log.paths[0].samples.len(), REQUESTS_PER_TICK);
+        assert_eq!(log.paths[0].stats().p99, 200.0);
+        assert_eq!(app.world.resource::<GameResources>().reputation, GameResources::default().reputation);
+    }
+
+    #[test]
+    fn test_slo_breach_costs_reputation() {
+        // Two nodes at 300ms each blow through the 400ms SLO on every request
+        let app = run_tick(300.0);
+        let log = app.world.resource::<LatencyLog>();
+        assert_eq!(log.paths[0].slow, REQUESTS_PER_TICK);
+        assert_eq!(log.breaches(SimulationConfig::default().latency_slo_ms).len(), 1);
+        assert_eq!(
+            app.world.resource::<GameResources>().reputation,
+            GameResources::default().reputation - SLO_REPUTATION_LOSS,
+        );
+    }
+}
+
+// Easter egg: "Every request is a tiny pilgrimage to the database and back 🛤️"
@@ -1,3 +1,32 @@
 pub mod game_loop;
+pub mod cards;
+pub mod planning;
+pub mod sprint;
+pub mod failures;
+pub mod latency;
+pub mod revenue;
+pub mod defects;
+pub mod save;
+pub mod history;
 
-pub use game_loop::tick_system; 
\ No newline at end of file
+pub use game_loop::tick_system;
+pub use cards::{draw_hand, expire_effects};
+pub use planning::{open_planned_changes, freeze_planned_changes, apply_planned_changes, advance_migration};
+pub use failures::{failure_system, start_failure_log};
+pub use latency::{latency_system, start_latency_log};
+pub use revenue::{revenue_system, start_traffic_log};
+pub use defects::{defect_system, start_defect_tracking};
+pub use history::{start_graph_history, record_graph_history};
+pub use save::{quick_save, quick_load, QUICK_SAVE_KEY, QUICK_LOAD_KEY};
+pub use sprint::{
+    setup_game,
+    setup_planning_phase,
+    setup_execution_phase,
+    update_planning_phase,
+    update_execution_phase,
+    capture_sprint_baseline,
+    report_sprint,
+    advance_sprint,
+    sprint_in_progress,
+    check_end_conditions,
+}; 
\ No newline at end of file
@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+use crate::cards::{Deck, ActiveEffects};
+use crate::components::SystemGraph;
+use crate::resources::{
+    PlannedChanges, PlannedAction, ExecutionPhase, DefectTracker, GameResources, ArchitectureMigration,
+    execute_actions,
+};
+
+// A new planning phase accepts changes again
+pub fn open_planned_changes(mut planned: ResMut<PlannedChanges>) {
+    planned.reopen();
+}
+
+// No changes allowed once planning is over
+pub fn freeze_planned_changes(mut planned: ResMut<PlannedChanges>) {
+    planned.freeze();
+    info!("Planning closed with {} queued changes", planned.actions().len());
+}
+
+// The "Apply Changes" execution step: apply everything queued during planning,
+// or nothing at all if any change is invalid
+pub fn apply_planned_changes(
+    mut planned: ResMut<PlannedChanges>,
+    mut deck: ResMut<Deck>,
+    mut effects: ResMut<ActiveEffects>,
+    mut phase: ResMut<ExecutionPhase>,
+    mut defects: ResMut<DefectTracker>,
+    mut query: Query<&mut SystemGraph>,
+) {
+    // Resuming from pause doesn't start a new execution phase
+    let Some(actions) = planned.take_frozen() else {
+        return;
+    };
+    phase.reset();
+
+    if let Ok(mut system) = query.get_single_mut() {
+        match execute_actions(&mut system, &mut effects, &mut defects, &deck.played, &actions) {
+            Ok(fixes) => {
+                info!("Applied {} planned changes", actions.len());
+                for (card, fixed) in fixes {
+                    info!("{} fixed {} defects", card, fixed);
+                }
+                for card in actions.into_iter().filter_map(PlannedAction::into_card) {
+                    deck.record_played(card);
+                }
+            }
+            Err(err) => {
+                error!("Rolled back planned changes: {}", err);
+                deck.discard_pile.extend(actions.into_iter().filter_map(PlannedAction::into_card));
+            }
+        }
+    }
+
+    phase.advance();
+}
+
+// Runs after the planned changes: a migration in progress takes its next step
+// with the sprint, once per sprint even if execution is paused and resumed
+pub fn advance_migration(
+    mut migration: ResMut<ArchitectureMigration>,
+    mut resources: ResMut<GameResources>,
+    mut query: Query<&mut SystemGraph>,
+) {
+    let Ok(mut system) = query.get_single_mut() else {
+        return;
+    };
+    let Some(progress) = migration.advance(&mut system, &mut resources) else {
+        return;
+    };
+    info!(
+        "Migration step: introduced [{}], decommissioned [{}]",
+        progress.introduced.join(", "),
+        progress.decommissioned.join(", ")
+    );
+    if progress.finished {
+        info!("Migration to {} complete", resources.current_architecture.name());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::starter_deck;
+    use crate::test_utils::create_test_graph;
+    use crate::GameState;
+
+    fn setup_app() -> App {
+        let mut app = App::new();
+        app.add_state::<GameState>()
+            .init_resource::<PlannedChanges>()
+            .init_resource::<Deck>()
+            .init_resource::<ActiveEffects>()
+            .init_resource::<ExecutionPhase>()
+            .init_resource::<DefectTracker>()
+            .init_resource::<GameResources>()
+            .init_resource::<ArchitectureMigration>()
+            .add_systems(OnEnter(GameState::Planning), open_planned_changes)
+            .add_systems(OnExit(GameState::Planning), freeze_planned_changes)
+            .add_systems(OnEnter(GameState::Running), (
+                apply_planned_changes,
+                advance_migration.after(apply_planned_changes),
+            ));
+        app.world.spawn(create_test_graph());
+        app
+    }
+
+    fn set_state(app: &mut App, state: GameState) {
+        app.world.resource_mut::<NextState<GameState>>().set(state);
+        app.update();
+    }
+
+    #[test]
+    fn test_changes_applied_on_execution() {
+        let mut app = setup_app();
+        set_state(&mut app, GameState::Planning);
+
+        let card = starter_deck().into_iter().find(|c| c.name == "Refactor System").unwrap();
+        app.world.resource_mut::<PlannedChanges>()
+            .queue(PlannedAction::PlayCard(card), 5)
+            .unwrap();
+
+        set_state(&mut app, GameState::Running);
+
+        let system = app.world.query::<&SystemGraph>().single(&app.world);
+        assert_eq!(system.graph[system.node_indices["test_node_1"]].tech_debt, 0.0);
+        assert_eq!(app.world.resource::<Deck>().played, vec!["Refactor System".to_string()]);
+        assert_eq!(app.world.resource::<ExecutionPhase>().current(), Some("Run Simulation"));
+
+        // Late changes are refused until the next planning phase
+        let card = starter_deck().into_iter().find(|c| c.name == "Hotfix").unwrap();
+        assert!(app.world.resource_mut::<PlannedChanges>()
+            .queue(PlannedAction::PlayCard(card), 5)
+            .is_err());
+    }
+
+    #[test]
+    fn test_bug_bash_fixes_defects() {
+        use crate::components::Severity;
+
+        let mut app = setup_app();
+        set_state(&mut app, GameState::Planning);
+        {
+            let mut defects = app.world.resource_mut::<DefectTracker>();
+            for severity in [Severity::Low, Severity::Medium, Severity::High, Severity::Critical] {
+                defects.open("test_node_1", severity, 1);
+            }
+        }
+
+        let card = starter_deck().into_iter().find(|c| c.name == "Bug Bash").unwrap();
+        app.world.resource_mut::<PlannedChanges>()
+            .queue(PlannedAction::PlayCard(card), 5)
+            .unwrap();
+        set_state(&mut app, GameState::Running);
+
+        let defects = app.world.resource::<DefectTracker>();
+        let open: Vec<_> = defects.open_defects().map(|d| d.severity).collect();
+        assert_eq!(open, vec![Severity::Low]);
+    }
+
+    #[test]
+    fn test_migration_steps_once_per_sprint() {
+        use crate::components::ArchitectureType;
+
+        let mut app = setup_app();
+        app.world.resource_mut::<ArchitectureMigration>()
+            .start(&ArchitectureType::Monolith.into(), ArchitectureType::Microservices, 3)
+            .unwrap();
+        set_state(&mut app, GameState::Planning);
+        set_state(&mut app, GameState::Running);
+        assert!(app.world.query::<&SystemGraph>().single(&app.world).node("api_gateway").is_some());
+
+        // Resuming from a pause is the same sprint
+        set_state(&mut app, GameState::Paused);
+        set_state(&mut app, GameState::Running);
+        let system = app.world.query::<&SystemGraph>().single(&app.world);
+        assert_eq!(system.graph.node_count(), 3);
+        assert_eq!(app.world.resource::<ArchitectureMigration>().plan().unwrap().completed, 1);
+    }
+}
+
+// Easter egg: "Plans are worthless, but planning is everything. Rollbacks are priceless 🗺️"
@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rand_distr::{Distribution, Poisson};
+use crate::components::{SystemGraph, request_paths, path_latency};
+use crate::resources::{
+    GameResources, FailureLog, CustomerModel, TrafficLog, RequestOutcome, SimulationConfig, SimRng,
+};
+
+pub fn start_traffic_log(mut log: ResMut<TrafficLog>) {
+    log.start_sprint();
+}
+
+// The "Calculate Revenue" step, one tick at a time: customers send requests
+// down random paths, and the ones that touch the critical path pay (or cost) money
+pub fn revenue_system(
+    config: Res<SimulationConfig>,
+    customers: Res<CustomerModel>,
+    failures: Res<FailureLog>,
+    mut rng: ResMut<SimRng>,
+    mut traffic: ResMut<TrafficLog>,
+    mut resources: ResMut<GameResources>,
+    query: Query<&SystemGraph>,
+) {
+    let Ok(system) = query.get_single() else {
+        return;
+    };
+    let paths = request_paths(system);
+    if paths.is_empty() {
+        return;
+    }
+
+    let expected = customers.traffic(resources.reputation) * config.tick_secs();
+    let requests = match Poisson::new(expected) {
+        Ok(poisson) => poisson.sample(rng.as_mut()) as u64,
+        Err(_) => 0,
+    };
+
+    for _ in 0..requests {
+        let path = &paths[rng.gen_range(0..paths.len())];
+        let outcome = if path.iter().any(|name| failures.is_down(name)) {
+            RequestOutcome::Failed
+        } else if path_latency(system, path, rng.as_mut()) > config.latency_slo_ms {
+            RequestOutcome::Slow
+        } else {
+            RequestOutcome::Succeeded
+        };
+
+        // Internal paths that never touch the critical path don't make money
+        let pays = path.iter().any(|name| system.node(name).is_some_and(|node| node.critical_path));
+        let revenue = if pays { customers.revenue(outcome) } else { 0.0 };
+        resources.money += revenue;
+        traffic.record(outcome, revenue);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{DistributionType, Outages};
+    use crate::test_utils::create_test_graph;
+
+    fn setup_app(failure_rate: f64) -> App {
+        let mut system = create_test_graph();
+        for node in system.graph.node_weights_mut() {
+            node.latency = DistributionType::Normal { mean: 50.0, std_dev: 0.0 };
+            node.failure_rate = DistributionType::Normal { mean: failure_rate, std_dev: 0.0 };
+        }
+
+        let mut app = App::new();
+        app.insert_resource(SimulationConfig { ticks_per_sprint: 10, ..Default::default() })
+            .init_resource::<SimRng>()
+            .init_resource::<CustomerModel>()
+            .init_resource::<FailureLog>()
+            .init_resource::<TrafficLog>()
+            .init_resource::<GameResources>()
+            .add_systems(Update, revenue_system);
+        app.world.spawn(system);
+        app
+    }
+
+    #[test]
+    fn test_healthy_system_earns_money() {
+        let mut app = setup_app(0.0);
+        app.update();
+
+        let traffic = app.world.resource::<TrafficLog>().clone();
+        assert!(traffic.requests() > 0);
+        assert_eq!(traffic.requests(), traffic.succeeded);
+        assert_eq!(traffic.net_revenue(), traffic.succeeded as f64 * CustomerModel::default().revenue_per_request);
+
+        let money = app.world.resource::<GameResources>().money;
+        assert_eq!(money, GameResources::default().money + traffic.net_revenue());
+    }
+
+    #[test]
+    fn test_outage_loses_money() {
+        let mut app = setup_app(1.0e6);
+        {
+            // Take everything down before customers show up
+            let system = app.world.query::<&SystemGraph>().single(&app.world).clone();
+            let mut outages = Outages::default();
+            let report = outages.step(&system, 0.1, &mut SimRng::new(9));
+            app.world.resource_mut::<FailureLog>().record(&report, 0.1, 0.0);
+        }
+        app.update();
+
+        let traffic = app.world.resource::<TrafficLog>();
+        assert!(traffic.failed > 0);
+        assert_eq!(traffic.earned, 0.0);
+        assert!(app.world.resource::<GameResources>().money < GameResources::default().money);
+    }
+}
+
+// Easter egg: "Revenue is just latency that customers didn't notice 💸"
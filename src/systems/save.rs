@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+use crate::resources::{SaveGame, PlanningTimer, QUICK_SAVE_PATH};
+
+pub const QUICK_SAVE_KEY: KeyCode = KeyCode::F5;
+pub const QUICK_LOAD_KEY: KeyCode = KeyCode::F9;
+
+fn key_pressed(world: &World, key: KeyCode) -> bool {
+    world.get_resource::<Input<KeyCode>>().is_some_and(|input| input.just_pressed(key))
+}
+
+// Save between sprints only: mid-sprint outages and logs aren't part of a save
+pub fn quick_save(world: &mut World) {
+    if !key_pressed(world, QUICK_SAVE_KEY) {
+        return;
+    }
+    match SaveGame::capture(world).and_then(|save| save.save(QUICK_SAVE_PATH)) {
+        Ok(()) => info!("💾 Quick-saved to {}", QUICK_SAVE_PATH),
+        Err(err) => error!("Quick-save failed: {}", err),
+    }
+}
+
+pub fn quick_load(world: &mut World) {
+    if !key_pressed(world, QUICK_LOAD_KEY) {
+        return;
+    }
+    match SaveGame::load(QUICK_SAVE_PATH) {
+        Ok(save) => {
+            let sprint = save.resources.sprint;
+            save.restore(world);
+            if let Some(mut timer) = world.get_resource_mut::<PlanningTimer>() {
+                timer.reset();
+            }
+            info!("📂 Quick-loaded sprint {} from {}", sprint, QUICK_SAVE_PATH);
+        }
+        Err(err) => error!("Quick-load failed: {}", err),
+    }
+}
+
+// Easter egg: "F5: the original disaster recovery plan 🔄"
@@ -0,0 +1,373 @@
+use bevy::prelude::*;
+use crate::cards::Deck;
+use crate::components::{
+    SystemGraph, Architecture, ArchitectureType, TemplateLibrary, GENERATED_ARCHITECTURE, generate_architecture,
+};
+use crate::resources::{
+    GameResources, ExecutionPhase, PlanningTimer, FailureLog, DefectTracker, TrafficLog, FinalScore,
+    SprintBaseline, SprintReport, SprintHistory, SimulationConfig, SimRng, create_initial_system,
+};
+use crate::GameState;
+
+// Initialize game state and spawn the initial system, unless one was provided up front
+#[allow(clippy::too_many_arguments)]
+pub fn setup_game(
+    mut commands: Commands,
+    mut resources: ResMut<GameResources>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut deck: ResMut<Deck>,
+    mut rng: ResMut<SimRng>,
+    config: Res<SimulationConfig>,
+    library: Res<TemplateLibrary>,
+    existing: Query<&SystemGraph>,
+) {
+    info!("Setting up initial game state");
+
+    if existing.is_empty() {
+        let (system, architecture) = starting_system(&config, &library, rng.as_mut());
+        resources.current_architecture = architecture;
+        info!("Created initial system with {} nodes and {} edges",
+            system.graph.node_count(),
+            system.graph.edge_count()
+        );
+        commands.spawn(system);
+    }
+    deck.shuffle(rng.as_mut());
+
+    // Start by planning the first sprint
+    next_state.set(GameState::Planning);
+    info!("Set game state to Planning");
+}
+
+// Build the configured starting architecture, falling back to the classic monolith
+fn starting_system(config: &SimulationConfig, library: &TemplateLibrary, rng: &mut SimRng) -> (SystemGraph, Architecture) {
+    let fallback = || (create_initial_system(), Architecture::Builtin(ArchitectureType::Monolith));
+    let architecture = config.architecture.as_str();
+    if architecture.eq_ignore_ascii_case(GENERATED_ARCHITECTURE) {
+        return (generate_architecture(&config.generator, rng), Architecture::Custom(GENERATED_ARCHITECTURE.into()));
+    }
+    let Some(template) = library.get(architecture) else {
+        warn!("Unknown architecture '{}', starting with the default system", architecture);
+        return fallback();
+    };
+    match template.build() {
+        Ok(system) => (system, Architecture::named(&template.name)),
+        Err(errors) => {
+            for error in errors {
+                error!("Architecture '{}': {}", template.name, error);
+            }
+            fallback()
+        }
+    }
+}
+
+pub fn setup_planning_phase(resources: Res<GameResources>, mut timer: ResMut<PlanningTimer>) {
+    info!("Starting planning phase for sprint {}", resources.sprint);
+    timer.reset();
+}
+
+pub fn setup_execution_phase() {
+    info!("Starting execution phase");
+}
+
+pub fn update_planning_phase(
+    time: Res<Time>,
+    config: Res<SimulationConfig>,
+    resources: Res<GameResources>,
+    mut timer: ResMut<PlanningTimer>,
+    mut next_state: ResMut<NextState<GameState>>,
+    query: Query<&SystemGraph>,
+) {
+    // Debug info
+    if let Ok(system) = query.get_single() {
+        debug!(
+            "System Status - Nodes: {}, Edges: {}, Money: ${:.2}, Sprint: {}",
+            system.graph.node_count(),
+            system.graph.edge_count(),
+            resources.money,
+            resources.sprint
+        );
+    }
+
+    // The sprint starts with or without the team once planning time is up
+    if timer.tick(time.delta_seconds_f64(), config.planning_time_limit_secs) {
+        info!("Planning time is up, starting sprint {}", resources.sprint);
+        next_state.set(GameState::Running);
+    }
+}
+
+// Remember where things stood as the sprint starts, for the sprint report
+pub fn capture_sprint_baseline(
+    resources: Res<GameResources>,
+    mut baseline: ResMut<SprintBaseline>,
+    query: Query<&SystemGraph>,
+) {
+    *baseline = SprintBaseline::capture(&resources, query.get_single().ok());
+}
+
+// Run condition for the fixed-step systems: stop ticking once the sprint is
+// used up, even if the switch back to planning only lands next frame
+pub fn sprint_in_progress(
+    execution_phase: Res<ExecutionPhase>,
+    config: Res<SimulationConfig>,
+) -> bool {
+    execution_phase.ticks < config.ticks_per_sprint
+}
+
+// Advance the sprint clock by one fixed tick
+pub fn update_execution_phase(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut execution_phase: ResMut<ExecutionPhase>,
+    config: Res<SimulationConfig>,
+    resources: Res<GameResources>,
+) {
+    execution_phase.tick(config.tick_secs());
+
+    // Print debug info every simulated second
+    let tick_secs = config.tick_secs();
+    if execution_phase.elapsed_time.floor() > (execution_phase.elapsed_time - tick_secs).floor() {
+        info!(
+            "Execution Phase - Step: {}/{}, Money: ${:.2}, Time: {:.1}s",
+            execution_phase.current_step + 1,
+            execution_phase.steps.len(),
+            resources.money,
+            execution_phase.elapsed_time
+        );
+    }
+
+    // Once the sprint has run its course, review it before planning the next one
+    if execution_phase.ticks >= config.ticks_per_sprint {
+        next_state.set(GameState::SprintReview);
+        info!("Execution phase complete after {} ticks, starting sprint review", execution_phase.ticks);
+    }
+}
+
+// Sprint end: write up the sprint report and announce it
+#[allow(clippy::too_many_arguments)]
+pub fn report_sprint(
+    execution_phase: Res<ExecutionPhase>,
+    config: Res<SimulationConfig>,
+    baseline: Res<SprintBaseline>,
+    resources: Res<GameResources>,
+    failures: Res<FailureLog>,
+    defects: Res<DefectTracker>,
+    traffic: Res<TrafficLog>,
+    mut history: ResMut<SprintHistory>,
+    mut reports: EventWriter<SprintReport>,
+    query: Query<&SystemGraph>,
+) {
+    if execution_phase.ticks < config.ticks_per_sprint {
+        return;
+    }
+    let Ok(system) = query.get_single() else {
+        return;
+    };
+
+    let report = SprintReport::new(&baseline, &resources, system, &failures, &defects, &traffic);
+    info!(
+        "Sprint {} report: money {:+.2}, reputation {:+.1}, {} incidents, {} defects found",
+        report.sprint, report.money_delta, report.reputation_delta, report.incidents, report.defects_found
+    );
+    history.reports.push(report.clone());
+    reports.send(report);
+}
+
+// Leaving the review moves the game on to the next sprint
+pub fn advance_sprint(mut resources: ResMut<GameResources>) {
+    resources.sprint += 1;
+}
+
+// Sprint end: check the win/lose rules before the sprint review opens.
+// Runs right after `update_execution_phase`, so it can still overrule its
+// switch to the review.
+#[allow(clippy::too_many_arguments)]
+pub fn check_end_conditions(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+    execution_phase: Res<ExecutionPhase>,
+    config: Res<SimulationConfig>,
+    resources: Res<GameResources>,
+    failures: Res<FailureLog>,
+    defects: Res<DefectTracker>,
+    query: Query<&SystemGraph>,
+) {
+    if execution_phase.ticks < config.ticks_per_sprint {
+        return;
+    }
+    let Ok(system) = query.get_single() else {
+        return;
+    };
+    let sprint_secs = config.sprint_duration_secs;
+    let Some(reason) = config.rules.evaluate(&resources, system, &failures, &defects, sprint_secs) else {
+        return;
+    };
+
+    let score = FinalScore::new(reason, &resources, system, &defects);
+    info!("Game ended after sprint {}: {} (score {:.0})", resources.sprint, reason.title(), score.total());
+    next_state.set(if reason.is_victory() { GameState::Victory } else { GameState::GameOver });
+    commands.insert_resource(score);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::{EndReason, SPRINT_DURATION_SECS};
+    use crate::components::GeneratorParams;
+    use crate::test_utils::create_test_graph;
+
+    #[test]
+    fn test_starting_system_comes_from_template() {
+        let mut library = TemplateLibrary::builtin();
+        library.load_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/architectures"));
+        let mut rng = SimRng::new(1);
+        let config = |architecture: &str| SimulationConfig { architecture: architecture.into(), ..Default::default() };
+        let (system, architecture) = starting_system(&config("event-driven"), &library, &mut rng);
+        assert!(system.node("event_bus").is_some());
+        assert_eq!(architecture, ArchitectureType::EventDriven.into());
+
+        // Templates without an archetype of their own count as themselves
+        let (_, architecture) = starting_system(&config("serverless"), &library, &mut rng);
+        assert_eq!(architecture, Architecture::Custom("Serverless".into()));
+
+        // Unknown names fall back to the default system rather than an empty board
+        let (fallback, architecture) = starting_system(&config("Blockchain"), &library, &mut rng);
+        assert_eq!(fallback.graph.node_count(), create_initial_system().graph.node_count());
+        assert_eq!(architecture, ArchitectureType::Monolith.into());
+
+        let (generated, architecture) = starting_system(&config("generated"), &library, &mut rng);
+        assert_eq!(generated.graph.node_count(), GeneratorParams::default().nodes);
+        assert_eq!(architecture.name(), GENERATED_ARCHITECTURE);
+    }
+
+    #[test]
+    fn test_sprint_ends_after_configured_ticks() {
+        let mut app = App::new();
+        app.add_state::<GameState>()
+            .init_resource::<ExecutionPhase>()
+            .init_resource::<GameResources>()
+            .insert_resource(SimulationConfig { ticks_per_sprint: 4, ..Default::default() })
+            .add_systems(Update, update_execution_phase
+                .run_if(in_state(GameState::Running).and_then(sprint_in_progress)));
+        app.world.insert_resource(State::new(GameState::Running));
+
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<ExecutionPhase>().ticks, 3);
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Running);
+
+        app.update();
+        app.update();
+        let phase = app.world.resource::<ExecutionPhase>();
+        assert_eq!(phase.ticks, 4);
+        assert_eq!(phase.elapsed_time, SPRINT_DURATION_SECS);
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::SprintReview);
+    }
+
+    #[test]
+    fn test_sprint_report_and_review() {
+        let mut app = App::new();
+        app.add_state::<GameState>()
+            .add_event::<SprintReport>()
+            .init_resource::<ExecutionPhase>()
+            .init_resource::<FailureLog>()
+            .init_resource::<DefectTracker>()
+            .init_resource::<TrafficLog>()
+            .init_resource::<SprintHistory>()
+            .init_resource::<GameResources>()
+            .insert_resource(SimulationConfig { ticks_per_sprint: 2, ..Default::default() })
+            .add_systems(OnExit(GameState::Planning), capture_sprint_baseline)
+            .add_systems(OnExit(GameState::SprintReview), advance_sprint)
+            .add_systems(Update, (update_execution_phase, report_sprint).chain()
+                .run_if(in_state(GameState::Running).and_then(sprint_in_progress)));
+        app.init_resource::<SprintBaseline>();
+        app.world.spawn(create_test_graph());
+        app.world.insert_resource(State::new(GameState::Planning));
+        app.world.resource_mut::<NextState<GameState>>().set(GameState::Running);
+        app.update();
+
+        app.world.resource_mut::<GameResources>().money -= 300.0;
+        for _ in 0..2 {
+            app.update();
+        }
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::SprintReview);
+        let history = app.world.resource::<SprintHistory>();
+        assert_eq!(history.reports.len(), 1);
+        let report = history.latest().unwrap();
+        assert_eq!(report.sprint, 1);
+        assert_eq!(report.money_delta, -300.0);
+        assert_eq!(app.world.resource::<Events<SprintReport>>().len(), 1);
+
+        // Still sprint 1 until the review is closed
+        assert_eq!(app.world.resource::<GameResources>().sprint, 1);
+        app.world.resource_mut::<NextState<GameState>>().set(GameState::Planning);
+        app.update();
+        assert_eq!(app.world.resource::<GameResources>().sprint, 2);
+    }
+
+    #[test]
+    fn test_planning_timer_starts_the_sprint() {
+        let mut app = App::new();
+        app.add_state::<GameState>()
+            .init_resource::<Time>()
+            .init_resource::<GameResources>()
+            .init_resource::<PlanningTimer>()
+            .insert_resource(SimulationConfig { planning_time_limit_secs: Some(0.0), ..Default::default() })
+            .add_systems(Update, update_planning_phase.run_if(in_state(GameState::Planning)));
+        app.world.insert_resource(State::new(GameState::Planning));
+
+        app.update();
+        app.update();
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Running);
+
+        // Without a limit planning waits for the player
+        app.insert_resource(SimulationConfig { planning_time_limit_secs: None, ..Default::default() });
+        app.world.insert_resource(State::new(GameState::Planning));
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Planning);
+    }
+
+    fn sprint_end_app(resources: GameResources) -> App {
+        let mut app = App::new();
+        app.add_state::<GameState>()
+            .init_resource::<ExecutionPhase>()
+            .init_resource::<FailureLog>()
+            .init_resource::<DefectTracker>()
+            .insert_resource(resources)
+            .insert_resource(SimulationConfig { ticks_per_sprint: 2, ..Default::default() })
+            .add_systems(Update, (update_execution_phase, check_end_conditions).chain()
+                .run_if(in_state(GameState::Running).and_then(sprint_in_progress)));
+        app.world.insert_resource(State::new(GameState::Running));
+        app.world.spawn(create_test_graph());
+        app
+    }
+
+    #[test]
+    fn test_bankruptcy_ends_the_game_at_sprint_end() {
+        let mut app = sprint_end_app(GameResources { money: -100.0, ..Default::default() });
+
+        // Being in the red mid-sprint is survivable
+        app.update();
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Running);
+        assert!(app.world.get_resource::<FinalScore>().is_none());
+
+        app.update();
+        app.update();
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::GameOver);
+        assert_eq!(app.world.resource::<FinalScore>().reason, EndReason::Bankrupt);
+    }
+
+    #[test]
+    fn test_surviving_wins() {
+        let mut app = sprint_end_app(GameResources { sprint: 20, ..Default::default() });
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Victory);
+        assert!(app.world.resource::<FinalScore>().reason.is_victory());
+    }
+}
+
+// Easter egg: "Sprint zero is where estimates go to be born, sprint one is where they go to die 🏃"
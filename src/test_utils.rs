@@ -3,7 +3,7 @@ use crate::components::{SystemGraph, SystemNode, SystemEdge};
 pub fn create_test_graph() -> SystemGraph {
     let mut graph = SystemGraph::new();
     
-    let node1 = graph.add_node(SystemNode {
+    graph.add_node(SystemNode {
         name: "test_node_1".into(),
         health: 100.0,
         tech_debt: 10.0,
@@ -12,7 +12,7 @@ pub fn create_test_graph() -> SystemGraph {
         ..Default::default()
     });
     
-    let node2 = graph.add_node(SystemNode {
+    graph.add_node(SystemNode {
         name: "test_node_2".into(),
         health: 100.0,
         tech_debt: 0.0,